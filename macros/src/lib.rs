@@ -0,0 +1,371 @@
+//! The procedural macros of `truth-tester`, which
+//! are used through its `macros` feature
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// A Boolean Expression parsed at compile time, with the
+/// default spellings of `TokenLiterals`, into a `Compiled`
+/// expression that never allocates. Variables are indexed
+/// by the order in which they first appear, and their names
+/// can be read at compile time, like
+///
+/// ```ignore
+/// const VARS: [&str; 2] = bool_expr!(a & !b).vars();
+/// ```
+///
+/// Only the spellings that Rust can tokenize are available,
+/// `!`, `~`, `&&`, `&`, `*`, `^`, `||`, `|`, `+`, `->`, `=>`,
+/// `<=>`, `==` and `=`, along with the words `not`, `and`,
+/// `xor`, `or`, `true` and `false`. Parenthesis, brackets
+/// and braces all group.
+#[proc_macro]
+pub fn bool_expr(input: TokenStream) -> TokenStream {
+    let toks = match lex(input) {
+        Ok(toks) => toks,
+        Err(e) => return e.into_compile_error(),
+    };
+
+    // a missing operand at the end is pointed at the
+    // last token, rather than at the whole macro call
+    let end = toks.last().map_or_else(Span::call_site, Tok::span);
+    let mut vars = Vec::new();
+    let expr = match Parser::new(&toks, end).all(&mut vars) {
+        Ok(expr) => expr,
+        Err(e) => return e.into_compile_error(),
+    };
+
+    expand(&vars, &expr)
+        .parse()
+        .expect("The generated code is valid Rust")
+}
+
+/// The code of the `Compiled` expression, a block that
+/// can be used in constants
+fn expand(vars: &[String], expr: &Expr) -> String {
+    let names: Vec<String> = vars.iter().map(|v| format!("{:?}", v)).collect();
+    format!(
+        "{{
+            #[allow(unused_variables)]
+            fn eval(var: &dyn Fn(usize) -> bool) -> bool {{
+                {}
+            }}
+            ::truth_tester::eval::Compiled::<{}>::new([{}], eval)
+        }}",
+        expr.to_rust(),
+        vars.len(),
+        names.join(", ")
+    )
+}
+
+/// A message to be shown at the place it is about
+struct Error {
+    msg: String,
+    span: Span,
+}
+
+impl Error {
+    fn new(msg: impl Into<String>, span: Span) -> Self {
+        Self {
+            msg: msg.into(),
+            span,
+        }
+    }
+
+    /// A `compile_error!` pointing at the span
+    fn into_compile_error(self) -> TokenStream {
+        let mut msg = Literal::string(&self.msg);
+        msg.set_span(self.span);
+        let mut args = Group::new(Delimiter::Parenthesis, TokenTree::from(msg).into());
+        args.set_span(self.span);
+        let mut bang = Punct::new('!', Spacing::Alone);
+        bang.set_span(self.span);
+
+        vec![
+            TokenTree::from(Ident::new("compile_error", self.span)),
+            bang.into(),
+            args.into(),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
+
+/// The binary operators, from the loosest binding
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Equality,
+    Implication,
+    Or,
+    Xor,
+    And,
+}
+
+impl Op {
+    fn precedence(self) -> usize {
+        self as usize
+    }
+}
+
+/// A token of the expression, along with where it is
+#[derive(Debug)]
+enum Tok {
+    Lit(bool, Span),
+    Var(String, Span),
+    Not(Span),
+    Op(Op, Span),
+    Group(Vec<Tok>, Span),
+}
+
+impl Tok {
+    fn span(&self) -> Span {
+        match self {
+            Tok::Lit(_, s) | Tok::Var(_, s) | Tok::Not(s) | Tok::Op(_, s) | Tok::Group(_, s) => *s,
+        }
+    }
+}
+
+/// The operators written with punctuation, where
+/// `None` is Not, of which the longest match is taken
+const PUNCTS: [(&str, Option<Op>); 14] = [
+    ("!", None),
+    ("~", None),
+    ("&&", Some(Op::And)),
+    ("&", Some(Op::And)),
+    ("*", Some(Op::And)),
+    ("^", Some(Op::Xor)),
+    ("||", Some(Op::Or)),
+    ("|", Some(Op::Or)),
+    ("+", Some(Op::Or)),
+    ("->", Some(Op::Implication)),
+    ("=>", Some(Op::Implication)),
+    ("<=>", Some(Op::Equality)),
+    ("==", Some(Op::Equality)),
+    ("=", Some(Op::Equality)),
+];
+
+fn lex(input: TokenStream) -> Result<Vec<Tok>, Error> {
+    let trees: Vec<TokenTree> = input.into_iter().collect();
+    let mut toks = Vec::new();
+    let mut idx = 0;
+
+    while idx < trees.len() {
+        match &trees[idx] {
+            TokenTree::Group(g) => toks.push(Tok::Group(lex(g.stream())?, g.span())),
+            TokenTree::Ident(i) => {
+                let name = i.to_string();
+                toks.push(match name.to_ascii_lowercase().as_str() {
+                    "true" => Tok::Lit(true, i.span()),
+                    "false" => Tok::Lit(false, i.span()),
+                    "not" => Tok::Not(i.span()),
+                    "and" => Tok::Op(Op::And, i.span()),
+                    "xor" => Tok::Op(Op::Xor, i.span()),
+                    "or" => Tok::Op(Op::Or, i.span()),
+                    // raw identifiers let the words be variables
+                    _ => Tok::Var(name.trim_start_matches("r#").into(), i.span()),
+                });
+            }
+            TokenTree::Punct(p) => {
+                // the characters of the operator, which Rust
+                // splits into one punctuation per character
+                let mut spelled = String::new();
+                let mut end = idx;
+                while let Some(TokenTree::Punct(p)) = trees.get(end) {
+                    spelled.push(p.as_char());
+                    end += 1;
+                    if p.spacing() == Spacing::Alone {
+                        break;
+                    }
+                }
+
+                let found = PUNCTS
+                    .iter()
+                    .filter(|(s, _)| spelled.starts_with(s))
+                    .max_by_key(|(s, _)| s.len())
+                    .map(|(s, op)| (s.len(), *op));
+
+                match found {
+                    Some((len, op)) => {
+                        toks.push(match op {
+                            Some(op) => Tok::Op(op, p.span()),
+                            None => Tok::Not(p.span()),
+                        });
+                        idx += len;
+                        continue;
+                    }
+                    None => {
+                        let msg = format!("`{}` is not an operator", p.as_char());
+                        return Err(Error::new(msg, p.span()));
+                    }
+                }
+            }
+            TokenTree::Literal(l) => {
+                let msg = format!("expected a variable, found `{}`", l);
+                return Err(Error::new(msg, l.span()));
+            }
+        }
+        idx += 1;
+    }
+
+    Ok(toks)
+}
+
+/// An expression tree, with variables by index
+enum Expr {
+    Lit(bool),
+    Var(usize),
+    Not(Box<Expr>),
+    Op(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// The expression as Rust, reading variables through `var`
+    fn to_rust(&self) -> String {
+        match self {
+            Expr::Lit(b) => b.to_string(),
+            Expr::Var(v) => format!("var({})", v),
+            Expr::Not(e) => format!("!({})", e.to_rust()),
+            Expr::Op(op, l, r) => {
+                let (l, r) = (l.to_rust(), r.to_rust());
+                match op {
+                    Op::And => format!("({}) & ({})", l, r),
+                    Op::Xor => format!("({}) ^ ({})", l, r),
+                    Op::Or => format!("({}) | ({})", l, r),
+                    Op::Implication => format!("!({}) | ({})", l, r),
+                    Op::Equality => format!("({}) == ({})", l, r),
+                }
+            }
+        }
+    }
+}
+
+/// Precedence climbing over a list of tokens, where operators
+/// of the same precedence associate to the right, like in
+/// the runtime parser
+struct Parser<'t> {
+    toks: &'t [Tok],
+    pos: usize,
+    /// where the tokens end, for errors about missing operands
+    end: Span,
+}
+
+impl<'t> Parser<'t> {
+    fn new(toks: &'t [Tok], end: Span) -> Self {
+        Self { toks, pos: 0, end }
+    }
+
+    /// The whole of the tokens as a single expression
+    fn all(&mut self, vars: &mut Vec<String>) -> Result<Expr, Error> {
+        let expr = self.expr(0, vars)?;
+        match self.toks.get(self.pos) {
+            None => Ok(expr),
+            Some(Tok::Op(..)) => unreachable!("Operators are always taken"),
+            Some(t) => Err(Error::new("expected an operator", t.span())),
+        }
+    }
+
+    fn expr(&mut self, min: usize, vars: &mut Vec<String>) -> Result<Expr, Error> {
+        let mut lhs = self.operand(vars)?;
+        while let Some(Tok::Op(op, _)) = self.toks.get(self.pos) {
+            if op.precedence() < min {
+                break;
+            }
+            self.pos += 1;
+            let rhs = self.expr(op.precedence(), vars)?;
+            lhs = Expr::Op(*op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn operand(&mut self, vars: &mut Vec<String>) -> Result<Expr, Error> {
+        let tok = self
+            .toks
+            .get(self.pos)
+            .ok_or_else(|| Error::new("expected a value", self.end))?;
+        self.pos += 1;
+
+        Ok(match tok {
+            Tok::Lit(b, _) => Expr::Lit(*b),
+            Tok::Var(name, _) => match vars.iter().position(|v| v == name) {
+                Some(v) => Expr::Var(v),
+                None => {
+                    vars.push(name.clone());
+                    Expr::Var(vars.len() - 1)
+                }
+            },
+            Tok::Not(_) => Expr::Not(Box::new(self.operand(vars)?)),
+            Tok::Group(inner, span) => {
+                if inner.is_empty() {
+                    return Err(Error::new("expected a value in the parens", *span));
+                }
+                Parser::new(inner, *span).all(vars)?
+            }
+            Tok::Op(_, span) => return Err(Error::new("expected a value", *span)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, Expr, Op};
+
+    fn var(v: usize) -> Box<Expr> {
+        Box::new(Expr::Var(v))
+    }
+
+    /// The code without its whitespace, which only the
+    /// indentation of the generated block is made of
+    fn compact(code: &str) -> String {
+        code.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn expands_operators() {
+        let cases = [
+            (Expr::Op(Op::And, var(0), var(1)), "(var(0)) & (var(1))"),
+            (Expr::Op(Op::Xor, var(0), var(1)), "(var(0)) ^ (var(1))"),
+            (Expr::Op(Op::Or, var(0), var(1)), "(var(0)) | (var(1))"),
+            (
+                Expr::Op(Op::Implication, var(0), var(1)),
+                "!(var(0)) | (var(1))",
+            ),
+            (
+                Expr::Op(Op::Equality, var(0), var(1)),
+                "(var(0)) == (var(1))",
+            ),
+            (Expr::Not(Box::new(Expr::Lit(true))), "!(true)"),
+        ];
+        for (expr, rust) in cases.iter() {
+            assert_eq!(expr.to_rust(), *rust);
+        }
+    }
+
+    #[test]
+    fn expands_nested_expressions() {
+        // a -> !(b = false)
+        let expr = Expr::Op(
+            Op::Implication,
+            var(0),
+            Box::new(Expr::Not(Box::new(Expr::Op(
+                Op::Equality,
+                var(1),
+                Box::new(Expr::Lit(false)),
+            )))),
+        );
+        assert_eq!(expr.to_rust(), "!(var(0)) | (!((var(1)) == (false)))");
+    }
+
+    #[test]
+    fn expands_into_a_compiled_expression() {
+        let vars = ["a".to_string(), "b".to_string()];
+        let expr = Expr::Op(Op::And, var(0), var(1));
+        assert_eq!(
+            compact(&expand(&vars, &expr)),
+            "{ #[allow(unused_variables)] \
+             fn eval(var: &dyn Fn(usize) -> bool) -> bool { (var(0)) & (var(1)) } \
+             ::truth_tester::eval::Compiled::<2>::new([\"a\", \"b\"], eval) }"
+        );
+        assert!(compact(&expand(&[], &Expr::Lit(true))).contains("Compiled::<0>::new([], eval)"));
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{
+    eval::{state::state_index, Expression, State, Tester, TruthTable},
+    parsing::{Token, Tokens},
+};
+use alloc::vec::Vec;
+
+/// The Algebraic Normal Form (Zhegalkin polynomial) of a
+/// function, or more generally one of its fixed polarity
+/// Reed–Muller forms.
+///
+/// The function is the XOR of all the monomials, where each
+/// monomial is the AND of the variables whose bits are set
+/// in it. An empty monomial is the constant `true`.
+///
+/// Variables whose bit is set in the [`polarity`] appear
+/// negated in every monomial. A polarity of `0` is the ANF.
+///
+/// [`polarity`]: `Anf::polarity`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anf {
+    var_count: usize,
+    polarity: usize,
+    monomials: Vec<usize>,
+}
+
+impl Anf {
+    /// Compute the ANF of the given table
+    pub fn from_table(table: &TruthTable) -> Self {
+        Self::with_polarity(table, 0)
+    }
+
+    /// Compute the fixed polarity Reed–Muller form of
+    /// the given table, negating every variable whose
+    /// bit is set in `polarity`
+    pub fn with_polarity(table: &TruthTable, polarity: usize) -> Self {
+        let var_count = table.var_count();
+        let polarity = polarity & (table.len() - 1);
+
+        let mut coeffs = if polarity == 0 {
+            table.clone()
+        } else {
+            // g(y) = f(y ^ polarity), so that the ANF of g is
+            // the ANF of f over the negated variables
+            let mut g = TruthTable::new(var_count);
+            for idx in 0..table.len() {
+                g.set(idx, table.get(idx ^ polarity));
+            }
+            g
+        };
+        mobius(coeffs.words_mut(), var_count);
+
+        let mut monomials: Vec<usize> = (0..coeffs.len()).filter(|&m| coeffs.get(m)).collect();
+        monomials.sort_by_key(|m| (m.count_ones(), *m));
+
+        Self {
+            var_count,
+            polarity,
+            monomials,
+        }
+    }
+
+    /// The amount of variables the form is over
+    pub fn var_count(&self) -> usize {
+        self.var_count
+    }
+
+    /// The variables that appear negated
+    pub fn polarity(&self) -> usize {
+        self.polarity
+    }
+
+    /// The monomials, as bit masks of the variables in them,
+    /// ordered by degree
+    pub fn monomials(&self) -> &[usize] {
+        &self.monomials
+    }
+
+    /// The algebraic degree, that is the amount of variables
+    /// in the largest monomial.
+    ///
+    /// The constant `false` function has a degree of `0`.
+    pub fn degree(&self) -> usize {
+        self.monomials
+            .iter()
+            .map(|m| m.count_ones() as usize)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Render the form as an expression made of
+    /// `Xor`, `And` and, for negated variables, `Not`
+    /// using the given variable names
+    pub fn to_tokens<'a>(&self, vars: &[&'a str]) -> Tokens<'a> {
+        use Token::*;
+        assert_eq!(
+            vars.len(),
+            self.var_count,
+            "Expected a name for each variable in the form"
+        );
+
+        // Operators are pushed after all their operands so that
+        // the chains associate to the right, like the parser does
+        let mut toks = Vec::new();
+        for &m in self.monomials.iter() {
+            if m == 0 {
+                toks.push(Literal(true));
+            } else {
+                for v in (0..self.var_count).filter(|v| m & (1 << v) != 0) {
+                    toks.push(Var(vars[v], v));
+                    if self.polarity & (1 << v) != 0 {
+                        toks.push(Not);
+                    }
+                }
+                for _ in 1..m.count_ones() {
+                    toks.push(And);
+                }
+            }
+        }
+        for _ in 1..self.monomials.len() {
+            toks.push(Xor);
+        }
+
+        if toks.is_empty() {
+            toks.push(Literal(false));
+        }
+
+        Tokens::new(toks, vars.to_vec())
+    }
+}
+
+impl Expression for Anf {
+    fn eval<S: State>(&self, state: S) -> bool {
+        let idx = state_index(&state, self.var_count) ^ self.polarity;
+        self.monomials.iter().filter(|&&m| idx & m == m).count() % 2 == 1
+    }
+}
+
+impl<E: Expression> Tester<E> {
+    /// Compute the Algebraic Normal Form of the expression
+    pub fn anf(&self) -> Anf {
+        Anf::from_table(&self.truth_table())
+    }
+
+    /// Compute the fixed polarity Reed–Muller form of the
+    /// expression, negating every variable whose bit
+    /// is set in `polarity`
+    pub fn reed_muller(&self, polarity: usize) -> Anf {
+        Anf::with_polarity(&self.truth_table(), polarity)
+    }
+}
+
+/// In place binary Möbius transform over a packed table
+fn mobius(words: &mut [u64], var_count: usize) {
+    // For each variable, the bits of the states
+    // where that variable is `false`
+    const MASKS: [u64; 6] = [
+        0x5555_5555_5555_5555,
+        0x3333_3333_3333_3333,
+        0x0F0F_0F0F_0F0F_0F0F,
+        0x00FF_00FF_00FF_00FF,
+        0x0000_FFFF_0000_FFFF,
+        0x0000_0000_FFFF_FFFF,
+    ];
+
+    // variables within a single word
+    for (v, mask) in MASKS.iter().enumerate().take(var_count) {
+        for w in words.iter_mut() {
+            *w ^= (*w & mask) << (1 << v);
+        }
+    }
+
+    // variables that span whole words
+    for v in 6..var_count {
+        let stride = 1 << (v - 6);
+        for chunk in words.chunks_mut(stride * 2) {
+            let (lo, hi) = chunk.split_at_mut(stride);
+            for (l, h) in lo.iter().zip(hi.iter_mut()) {
+                *h ^= *l;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Anf;
+    use crate::{
+        eval::Tester,
+        parsing::{Generator, SplitMix64},
+    };
+    use alloc::string::ToString;
+
+    fn anf(src: &str) -> Anf {
+        Tester::parse(src).anf()
+    }
+
+    #[test]
+    fn finds_known_forms() {
+        let cases: [(&str, &[usize], usize); 8] = [
+            ("a | b", &[0b01, 0b10, 0b11], 2),
+            ("a & b", &[0b11], 2),
+            ("a ^ b", &[0b01, 0b10], 1),
+            ("a = b", &[0b00, 0b01, 0b10], 1),
+            ("!a", &[0b0, 0b1], 1),
+            ("a -> b", &[0b00, 0b01, 0b11], 2),
+            ("a & b | a & c | b & c", &[0b011, 0b101, 0b110], 2),
+            ("a & b & c ^ a", &[0b001, 0b111], 3),
+        ];
+        for (src, monomials, degree) in cases.iter() {
+            let anf = anf(src);
+            assert_eq!(anf.monomials(), *monomials, "{}", src);
+            assert_eq!(anf.degree(), *degree, "{}", src);
+            assert_eq!(anf.polarity(), 0, "{}", src);
+        }
+
+        assert_eq!(anf("a & !a").monomials(), []);
+        assert_eq!(anf("a & !a").degree(), 0);
+        assert_eq!(anf("a | !a").monomials(), [0]);
+    }
+
+    #[test]
+    fn negates_variables_of_the_polarity() {
+        // a | b = !(!a & !b) = 1 ^ !a!b
+        let rm = Tester::parse("a | b").reed_muller(0b11);
+        assert_eq!(rm.polarity(), 0b11);
+        assert_eq!(rm.monomials(), [0b00, 0b11]);
+        // a & !b has a single monomial when b is negated
+        let rm = Tester::parse("a & !b").reed_muller(0b10);
+        assert_eq!(rm.monomials(), [0b11]);
+        // the polarity only keeps bits of the variables
+        assert_eq!(Tester::parse("a").reed_muller(0b110).polarity(), 0);
+    }
+
+    #[test]
+    fn renders_tokens() {
+        let tokens = anf("a | b").to_tokens(&["a", "b"]);
+        assert_eq!(tokens.to_string(), "a ^ b ^ a & b");
+        let tokens = Tester::parse("a | b")
+            .reed_muller(0b01)
+            .to_tokens(&["a", "b"]);
+        assert_eq!(tokens.to_string(), "true ^ !a ^ !a & b");
+        assert_eq!(anf("a & !a").to_tokens(&["a"]).to_string(), "false");
+    }
+
+    #[test]
+    fn keeps_the_truth_table() {
+        for var_count in 0..=8 {
+            let gen = Generator::new(var_count).depth(6);
+            for seed in 0..20 {
+                let tokens = gen.generate(SplitMix64::new(seed));
+                let tester = Tester::with_tokens(tokens.clone());
+                let table = tester.truth_table();
+                for polarity in [0, 0b1, 0b1010_0101].iter() {
+                    let rm = tester.reed_muller(*polarity);
+                    assert_eq!(
+                        Tester::with_tokens(rm.to_tokens(tokens.vars())).truth_table(),
+                        table,
+                        "{} with polarity {:b}",
+                        tokens,
+                        polarity
+                    );
+                    assert_eq!(
+                        Tester::<Anf>::new(rm, var_count).truth_table(),
+                        table,
+                        "{} with polarity {:b}",
+                        tokens,
+                        polarity
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    eval::{State, Tester},
+    parsing::Tokens,
+};
+use core::fmt;
+
+/// The values of every variable of an expression in a given
+/// state, displayed as `name=value` pairs like `a=1 b=0`
+pub struct Assignment<'a, 't, S: State> {
+    tester: &'a Tester<Tokens<'t>>,
+    state: S,
+}
+
+impl<'a, 't, S: State> fmt::Display for Assignment<'a, 't, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for n in 0..self.tester.var_count() {
+            if n != 0 {
+                f.write_str(" ")?;
+            }
+            let (name, val) = self.tester.var_at(self.state.clone(), n);
+            write!(f, "{}={}", name, val as u8)?;
+        }
+        Ok(())
+    }
+}
+
+/// The message given to an assertion macro, if any,
+/// written after a colon
+#[doc(hidden)]
+pub struct Message<'a>(pub Option<fmt::Arguments<'a>>);
+
+impl<'a> fmt::Display for Message<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(msg) => write!(f, ": {}", msg),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'t> Tester<Tokens<'t>> {
+    /// The values of every variable in the given
+    /// state, to be displayed as `name=value` pairs
+    pub fn assignment<S: State>(&self, state: S) -> Assignment<'_, 't, S> {
+        Assignment {
+            tester: self,
+            state,
+        }
+    }
+}
+
+/// Asserts that an expression is true for every assignment,
+/// panicking with the first one it is false for otherwise
+#[macro_export]
+macro_rules! assert_tautology {
+    (@ $expr:expr, $msg:expr) => {{
+        let expr: &str = $expr;
+        let tester = $crate::eval::Tester::<$crate::parsing::Tokens>::parse(expr);
+        let failure = tester.failures().next();
+        if let Some(state) = failure {
+            panic!(
+                "assertion failed: `{}` is not a tautology{}\n assignment: {}\n     result: 0",
+                expr,
+                $msg,
+                tester.assignment(state)
+            );
+        }
+    }};
+    ($expr:expr $(,)?) => {
+        $crate::assert_tautology!(@ $expr, $crate::eval::Message(None))
+    };
+    ($expr:expr, $($arg:tt)+) => {
+        $crate::assert_tautology!(@ $expr, $crate::eval::Message(Some(format_args!($($arg)+))))
+    };
+}
+
+/// Asserts that an expression is false for every assignment,
+/// panicking with the first one it is true for otherwise
+#[macro_export]
+macro_rules! assert_contradiction {
+    (@ $expr:expr, $msg:expr) => {{
+        let expr: &str = $expr;
+        let tester = $crate::eval::Tester::<$crate::parsing::Tokens>::parse(expr);
+        let success = tester.successes().next();
+        if let Some(state) = success {
+            panic!(
+                "assertion failed: `{}` is not a contradiction{}\n assignment: {}\n     result: 1",
+                expr,
+                $msg,
+                tester.assignment(state)
+            );
+        }
+    }};
+    ($expr:expr $(,)?) => {
+        $crate::assert_contradiction!(@ $expr, $crate::eval::Message(None))
+    };
+    ($expr:expr, $($arg:tt)+) => {
+        $crate::assert_contradiction!(@ $expr, $crate::eval::Message(Some(format_args!($($arg)+))))
+    };
+}
+
+/// Asserts that an expression is true for at least one
+/// assignment, panicking with the first one otherwise
+#[macro_export]
+macro_rules! assert_satisfiable {
+    (@ $expr:expr, $msg:expr) => {{
+        let expr: &str = $expr;
+        let tester = $crate::eval::Tester::<$crate::parsing::Tokens>::parse(expr);
+        let success = tester.successes().next();
+        if success.is_none() {
+            let failure = tester.failures().next();
+            panic!(
+                "assertion failed: `{}` is not satisfiable{}\n assignment: {}\n     result: 0",
+                expr,
+                $msg,
+                tester.assignment(failure.expect("There is always an assignment"))
+            );
+        }
+    }};
+    ($expr:expr $(,)?) => {
+        $crate::assert_satisfiable!(@ $expr, $crate::eval::Message(None))
+    };
+    ($expr:expr, $($arg:tt)+) => {
+        $crate::assert_satisfiable!(@ $expr, $crate::eval::Message(Some(format_args!($($arg)+))))
+    };
+}
+
+/// Asserts that two expressions give the same result for every
+/// assignment, matching their variables by name, panicking with
+/// the first assignment they differ for otherwise
+#[macro_export]
+macro_rules! assert_equivalent {
+    (@ $left:expr, $right:expr, $msg:expr) => {{
+        let (left, right): (&str, &str) = ($left, $right);
+        let l = $crate::eval::Tester::<$crate::parsing::Tokens>::parse(left);
+        let r = $crate::eval::Tester::<$crate::parsing::Tokens>::parse(right);
+        if let Some(cex) = l.counterexample(&r) {
+            panic!(
+                "assertion failed: `{}` is not equivalent to `{}`{}\n assignment: {}\n     result: {} and {}",
+                left,
+                right,
+                $msg,
+                cex,
+                cex.left() as u8,
+                cex.right() as u8
+            );
+        }
+    }};
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_equivalent!(@ $left, $right, $crate::eval::Message(None))
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::assert_equivalent!(@ $left, $right, $crate::eval::Message(Some(format_args!($($arg)+))))
+    };
+}
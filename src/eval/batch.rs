@@ -0,0 +1,386 @@
+use crate::{
+    eval::{Counterexample, Expression, Tester},
+    parsing::{ParseError, Parser, TokenLiterals, Tokens},
+};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// What a record expects of its expression
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Expectation<'a> {
+    /// True for every assignment
+    Tautology,
+    /// False for every assignment
+    Contradiction,
+    /// True for at least one assignment
+    Satisfiable,
+    /// The same as the expression of the named record
+    /// for every assignment, matching variables by name
+    Equivalent(&'a str),
+}
+
+impl<'a> Expectation<'a> {
+    /// The name of the expectation in a batch file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Expectation::Tautology => "tautology",
+            Expectation::Contradiction => "contradiction",
+            Expectation::Satisfiable => "satisfiable",
+            Expectation::Equivalent(_) => "equivalent",
+        }
+    }
+}
+
+/// A named expression, along with what it is expected to be
+pub struct Record<'a> {
+    name: &'a str,
+    line: usize,
+    expectation: Expectation<'a>,
+    tester: Tester<Tokens<'a>>,
+}
+
+impl<'a> Record<'a> {
+    /// The name of the record
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The line the record is on, counting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// What the expression is expected to be
+    pub fn expectation(&self) -> Expectation<'a> {
+        self.expectation
+    }
+
+    /// The expression
+    pub fn tester(&self) -> &Tester<Tokens<'a>> {
+        &self.tester
+    }
+}
+
+/// What is wrong with a line of a batch file
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum BatchErrorKind {
+    /// A line with a name but nothing else
+    MissingExpectation,
+    /// An expectation that is not one of the four
+    UnknownExpectation(String),
+    /// `equivalent` without the name of the other record
+    MissingOther,
+    /// A record without an expression
+    MissingExpression,
+    /// A malformed expression, the offset being into it
+    Expression(ParseError),
+    /// A name used by an earlier record
+    DuplicateName(String),
+    /// `equivalent` to a record that does not exist
+    UnknownName(String),
+}
+
+/// An error found while reading a batch file, along
+/// with the line, counting from 1, that it was found on
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct BatchError {
+    line: usize,
+    kind: BatchErrorKind,
+}
+
+impl BatchError {
+    /// The line the error was found on, counting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// What went wrong
+    pub fn kind(&self) -> &BatchErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            BatchErrorKind::MissingExpectation => f.write_str("expected an expectation"),
+            BatchErrorKind::UnknownExpectation(e) => write!(f, "unknown expectation `{}`", e),
+            BatchErrorKind::MissingOther => f.write_str("expected the name of a record"),
+            BatchErrorKind::MissingExpression => f.write_str("expected an expression"),
+            BatchErrorKind::Expression(e) => write!(f, "in the expression, {}", e),
+            BatchErrorKind::DuplicateName(n) => write!(f, "`{}` was already used", n),
+            BatchErrorKind::UnknownName(n) => write!(f, "there is no record `{}`", n),
+        }
+    }
+}
+
+/// Why a record did not meet its expectation
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Failure<'a> {
+    /// An assignment the expression gives the wrong result for
+    Assignment(Vec<(&'a str, bool)>),
+    /// No assignment makes the expression true
+    Unsatisfiable,
+    /// An assignment the expressions give different results for
+    Differs(Counterexample<'a>),
+}
+
+/// The outcome of verifying a single record
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Verdict<'a> {
+    name: &'a str,
+    line: usize,
+    expectation: Expectation<'a>,
+    failure: Option<Failure<'a>>,
+}
+
+impl<'a> Verdict<'a> {
+    /// The name of the record
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The line the record is on, counting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// What the expression was expected to be
+    pub fn expectation(&self) -> Expectation<'a> {
+        self.expectation
+    }
+
+    /// Whether the expectation was met
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    /// Why the expectation was not met, if it was not
+    pub fn failure(&self) -> Option<&Failure<'a>> {
+        self.failure.as_ref()
+    }
+}
+
+impl<'a> fmt::Display for Verdict<'a> {
+    /// Writes what was found, like `` `x` is not a tautology,
+    /// it is false for a=1 b=0 ``
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let not = if self.passed() { "" } else { "not " };
+        write!(f, "`{}` is {}", self.name, not)?;
+        match self.expectation {
+            Expectation::Tautology => f.write_str("a tautology")?,
+            Expectation::Contradiction => f.write_str("a contradiction")?,
+            Expectation::Satisfiable => f.write_str("satisfiable")?,
+            Expectation::Equivalent(other) => write!(f, "equivalent to `{}`", other)?,
+        }
+
+        match &self.failure {
+            None | Some(Failure::Unsatisfiable) => Ok(()),
+            Some(Failure::Assignment(vals)) => {
+                let result = self.expectation == Expectation::Contradiction;
+                write!(f, ", it is {}", result)?;
+                for (n, (name, val)) in vals.iter().enumerate() {
+                    let sep = if n == 0 { " for " } else { " " };
+                    write!(f, "{}{}={}", sep, name, *val as u8)?;
+                }
+                Ok(())
+            }
+            Some(Failure::Differs(cex)) => {
+                write!(
+                    f,
+                    ", they give {} and {}",
+                    cex.left() as u8,
+                    cex.right() as u8
+                )?;
+                if cex.assignment().is_empty() {
+                    Ok(())
+                } else {
+                    write!(f, " for {}", cex)
+                }
+            }
+        }
+    }
+}
+
+/// The verdicts of every record of a [`Batch`], in order
+///
+/// [`Batch`]: `Batch`
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Report<'a> {
+    verdicts: Vec<Verdict<'a>>,
+}
+
+impl<'a> Report<'a> {
+    /// The verdict of every record
+    pub fn verdicts(&self) -> &[Verdict<'a>] {
+        &self.verdicts
+    }
+
+    /// The amount of records that met their expectation
+    pub fn passed(&self) -> usize {
+        self.verdicts.iter().filter(|v| v.passed()).count()
+    }
+
+    /// The amount of records that did not
+    pub fn failed(&self) -> usize {
+        self.verdicts.len() - self.passed()
+    }
+
+    /// Whether every record met its expectation
+    pub fn is_success(&self) -> bool {
+        self.verdicts.iter().all(Verdict::passed)
+    }
+}
+
+/// Many named expressions, each with what it is expected to be
+pub struct Batch<'a> {
+    records: Vec<Record<'a>>,
+}
+
+impl<'a> Batch<'a> {
+    /// Read a batch file, see [`Batch::parse_with_literals`]
+    ///
+    /// [`Batch::parse_with_literals`]: `Batch::parse_with_literals`
+    pub fn parse(src: &'a str) -> Result<Self, BatchError> {
+        Self::parse_with_literals(src, TokenLiterals::default())
+    }
+
+    /// Read a batch file, where every line is a record made of
+    /// a name, an expectation and an infix expression, separated
+    /// by whitespace. The expectation is one of `tautology`,
+    /// `contradiction`, `satisfiable` or `equivalent` followed
+    /// by the name of another record, like
+    ///
+    /// ```text
+    /// # lines starting with `#` are comments
+    /// both       contradiction    open & !open
+    /// safe       satisfiable      !(open & locked)
+    /// safe_too   equivalent safe  !open | !locked
+    /// ```
+    pub fn parse_with_literals(src: &'a str, literals: TokenLiterals) -> Result<Self, BatchError> {
+        let mut records: Vec<Record<'a>> = Vec::new();
+
+        for (idx, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |kind| BatchError {
+                line: idx + 1,
+                kind,
+            };
+
+            let (name, rest) = split_word(line);
+            let (expectation, rest) = split_word(rest);
+            let (expectation, expr) = match expectation {
+                "" => return Err(err(BatchErrorKind::MissingExpectation)),
+                "tautology" => (Expectation::Tautology, rest),
+                "contradiction" => (Expectation::Contradiction, rest),
+                "satisfiable" => (Expectation::Satisfiable, rest),
+                "equivalent" => match split_word(rest) {
+                    ("", _) => return Err(err(BatchErrorKind::MissingOther)),
+                    (other, expr) => (Expectation::Equivalent(other), expr),
+                },
+                e => return Err(err(BatchErrorKind::UnknownExpectation(e.into()))),
+            };
+            if expr.is_empty() {
+                return Err(err(BatchErrorKind::MissingExpression));
+            }
+            if records.iter().any(|r| r.name == name) {
+                return Err(err(BatchErrorKind::DuplicateName(name.into())));
+            }
+
+            let tokens = Parser::parse_with_literals(expr, literals.clone())
+                .try_shunting_yard()
+                .map_err(|e| err(BatchErrorKind::Expression(e)))?;
+            records.push(Record {
+                name,
+                line: idx + 1,
+                expectation,
+                tester: Tester::with_tokens(tokens),
+            });
+        }
+
+        // records may be compared with those after them
+        for r in &records {
+            if let Expectation::Equivalent(other) = r.expectation {
+                if !records.iter().any(|o| o.name == other) {
+                    return Err(BatchError {
+                        line: r.line,
+                        kind: BatchErrorKind::UnknownName(other.into()),
+                    });
+                }
+            }
+        }
+
+        Ok(Self { records })
+    }
+
+    /// Every record, in the order they were read
+    pub fn records(&self) -> &[Record<'a>] {
+        &self.records
+    }
+
+    /// Check every record against its expectation
+    pub fn verify(&self) -> Report<'a> {
+        Report {
+            verdicts: self.records.iter().map(|r| self.verdict(r)).collect(),
+        }
+    }
+
+    /// Check every record against its expectation,
+    /// spreading the records over threads
+    #[cfg(feature = "parallel")]
+    pub fn verify_par(&self) -> Report<'a> {
+        use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+        Report {
+            verdicts: self.records.par_iter().map(|r| self.verdict(r)).collect(),
+        }
+    }
+
+    fn verdict(&self, record: &Record<'a>) -> Verdict<'a> {
+        let tester = &record.tester;
+        let assignment = |state: usize| {
+            let vars = tester.expr.vars().iter().copied();
+            Failure::Assignment(vars.zip(tester.var_vals(state)).collect())
+        };
+
+        let mut states = tester.iterations();
+        let failure = match record.expectation {
+            Expectation::Tautology => states.find(|&s| !tester.expr.eval(s)).map(assignment),
+            Expectation::Contradiction => states.find(|&s| tester.expr.eval(s)).map(assignment),
+            Expectation::Satisfiable => match states.find(|&s| tester.expr.eval(s)) {
+                Some(_) => None,
+                None => Some(Failure::Unsatisfiable),
+            },
+            Expectation::Equivalent(other) => {
+                let other = self
+                    .records
+                    .iter()
+                    .find(|o| o.name == other)
+                    .expect("Every name was checked while reading");
+                tester.counterexample(&other.tester).map(Failure::Differs)
+            }
+        };
+
+        Verdict {
+            name: record.name,
+            line: record.line,
+            expectation: record.expectation,
+            failure,
+        }
+    }
+}
+
+/// The first word of `s` and the rest of it, both trimmed,
+/// as batch files and the commands of the `truth-tester`
+/// shell are read
+pub fn split_word(s: &str) -> (&str, &str) {
+    let s = s.trim();
+    match s.find(char::is_whitespace) {
+        Some(idx) => (&s[..idx], s[idx..].trim()),
+        None => (s, ""),
+    }
+}
@@ -0,0 +1,244 @@
+//! Building blocks for expressions written by hand, which
+//! can be joined with `&`, `|`, `^` and `!`, like
+//! `(Var(0) & !Var(1)) | Implies(Var(2), Const(false))`
+
+use crate::eval::{Expression, State};
+use core::ops;
+
+/// The variable at an index of the state
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Var(pub usize);
+
+/// A value that does not depend on the state
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Const(pub bool);
+
+/// The negation of an expression, also written `!e`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Not<E>(pub E);
+
+/// True when both are, also written `a & b`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct And<A, B>(pub A, pub B);
+
+/// True when either is, also written `a | b`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Or<A, B>(pub A, pub B);
+
+/// True when exactly one is, also written `a ^ b`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Xor<A, B>(pub A, pub B);
+
+/// False only when the first is true and the second is not
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Implies<A, B>(pub A, pub B);
+
+/// True when both give the same result
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Iff<A, B>(pub A, pub B);
+
+/// True when every expression is, so also when there are none
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct All<T>(pub T);
+
+/// True when any expression is, so never when there are none
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Any<T>(pub T);
+
+/// An expression evaluated by a closure, see [`from_fn`]
+///
+/// [`from_fn`]: `from_fn`
+#[derive(Clone, Copy)]
+pub struct FromFn<F>(F);
+
+/// An expression evaluated by a closure, which is given the
+/// state as a function from the index of a variable to its value
+///
+/// The state is passed like this, rather than as `&S`, because
+/// expressions are evaluated for every type of [`State`]. Closures
+/// do not implement [`Expression`] themselves, as they would then
+/// overlap with `&E` and `Box<E>`, but a `&dyn Fn` or `Box<dyn Fn>`
+/// taking the same view of the state does, like
+///
+/// ```ignore
+/// let f: &dyn Fn(&dyn Fn(usize) -> bool) -> bool = &|var| var(0) || !var(1);
+/// Tester::with_expr(f, 2)
+/// ```
+///
+/// [`State`]: `State`
+/// [`Expression`]: `Expression`
+pub fn from_fn<F: Fn(&dyn Fn(usize) -> bool) -> bool>(f: F) -> FromFn<F> {
+    FromFn(f)
+}
+
+impl Expression for Var {
+    fn eval<S: State>(&self, state: S) -> bool {
+        state.var_at(self.0)
+    }
+}
+
+impl Expression for Const {
+    fn eval<S: State>(&self, _: S) -> bool {
+        self.0
+    }
+}
+
+impl<E: Expression> Expression for Not<E> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        !self.0.eval(state)
+    }
+}
+
+impl<A: Expression, B: Expression> Expression for And<A, B> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        self.0.eval(state.clone()) && self.1.eval(state)
+    }
+}
+
+impl<A: Expression, B: Expression> Expression for Or<A, B> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        self.0.eval(state.clone()) || self.1.eval(state)
+    }
+}
+
+impl<A: Expression, B: Expression> Expression for Xor<A, B> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        self.0.eval(state.clone()) ^ self.1.eval(state)
+    }
+}
+
+impl<A: Expression, B: Expression> Expression for Implies<A, B> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        !self.0.eval(state.clone()) || self.1.eval(state)
+    }
+}
+
+impl<A: Expression, B: Expression> Expression for Iff<A, B> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        self.0.eval(state.clone()) == self.1.eval(state)
+    }
+}
+
+impl<E: Expression, const N: usize> Expression for All<[E; N]> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        self.0.iter().all(|e| e.eval(state.clone()))
+    }
+}
+
+impl<E: Expression, const N: usize> Expression for Any<[E; N]> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        self.0.iter().any(|e| e.eval(state.clone()))
+    }
+}
+
+impl<F: Fn(&dyn Fn(usize) -> bool) -> bool> Expression for FromFn<F> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        (self.0)(&|v| state.var_at(v))
+    }
+}
+
+impl<'f> Expression for dyn Fn(&dyn Fn(usize) -> bool) -> bool + 'f {
+    fn eval<S: State>(&self, state: S) -> bool {
+        self(&|v| state.var_at(v))
+    }
+}
+
+impl<E: Expression + ?Sized> Expression for &E {
+    fn eval<S: State>(&self, state: S) -> bool {
+        (**self).eval(state)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Expression + ?Sized> Expression for alloc::boxed::Box<E> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        (**self).eval(state)
+    }
+}
+
+/// Lets the combinators be joined with `&`, `|`, `^` and `!`
+macro_rules! ops_impl {
+    ($([$($gen:ident),*] $t:ty),* $(,)?) => ($(
+        impl<$($gen,)* Rhs: Expression> ops::BitAnd<Rhs> for $t {
+            type Output = And<Self, Rhs>;
+
+            fn bitand(self, rhs: Rhs) -> Self::Output {
+                And(self, rhs)
+            }
+        }
+
+        impl<$($gen,)* Rhs: Expression> ops::BitOr<Rhs> for $t {
+            type Output = Or<Self, Rhs>;
+
+            fn bitor(self, rhs: Rhs) -> Self::Output {
+                Or(self, rhs)
+            }
+        }
+
+        impl<$($gen,)* Rhs: Expression> ops::BitXor<Rhs> for $t {
+            type Output = Xor<Self, Rhs>;
+
+            fn bitxor(self, rhs: Rhs) -> Self::Output {
+                Xor(self, rhs)
+            }
+        }
+
+        impl<$($gen),*> ops::Not for $t {
+            type Output = Not<Self>;
+
+            fn not(self) -> Self::Output {
+                Not(self)
+            }
+        }
+    )*)
+}
+
+ops_impl! {
+    [] Var,
+    [] Const,
+    [E] Not<E>,
+    [A, B] And<A, B>,
+    [A, B] Or<A, B>,
+    [A, B] Xor<A, B>,
+    [A, B] Implies<A, B>,
+    [A, B] Iff<A, B>,
+    [T] All<T>,
+    [T] Any<T>,
+    [F] FromFn<F>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use alloc::boxed::Box;
+
+    type Closure = dyn Fn(&dyn Fn(usize) -> bool) -> bool;
+
+    #[test]
+    fn dyn_closures_are_expressions() {
+        let f: &Closure = &|var| var(0) && !var(1);
+        let expected = Var(0) & !Var(1);
+        for state in 0..4u8 {
+            assert_eq!(f.eval(state), expected.eval(state), "{}", state);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn boxed_closures_are_expressions() {
+        let boxed: Box<Closure> = Box::new(|var| var(0) && !var(1));
+        let expected = Var(0) & !Var(1);
+        for state in 0..4u8 {
+            assert_eq!(boxed.eval(state), expected.eval(state), "{}", state);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn boxed_expressions_are_expressions() {
+        let boxed = Box::new(Var(0) | Var(1));
+        assert!(!boxed.eval(0u8));
+        assert!(boxed.eval(&[false, true][..]));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::eval::{Expression, State, Tester};
+
+/// An expression over `N` variables parsed at compile time
+/// by [`bool_expr!`], which is evaluated without allocating
+///
+/// [`bool_expr!`]: `crate::bool_expr`
+#[derive(Debug, Clone, Copy)]
+pub struct Compiled<const N: usize> {
+    vars: [&'static str; N],
+    eval: fn(&dyn Fn(usize) -> bool) -> bool,
+}
+
+impl<const N: usize> Compiled<N> {
+    #[doc(hidden)]
+    pub const fn new(vars: [&'static str; N], eval: fn(&dyn Fn(usize) -> bool) -> bool) -> Self {
+        Self { vars, eval }
+    }
+
+    /// The names of the variables, indexed by the order in
+    /// which they first appear, which can be read in a
+    /// constant, like
+    ///
+    /// ```ignore
+    /// const VARS: [&str; 2] = bool_expr!(a & !b).vars();
+    /// ```
+    pub const fn vars(&self) -> [&'static str; N] {
+        self.vars
+    }
+
+    /// The amount of variables
+    pub const fn var_count(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Expression for Compiled<N> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        (self.eval)(&|v| state.var_at(v))
+    }
+}
+
+impl<const N: usize> Tester<Compiled<N>> {
+    /// Test an expression parsed at compile time
+    pub fn with_compiled(expr: Compiled<N>) -> Self {
+        Self::new(expr, N)
+    }
+
+    /// The names of the variables of the expression
+    pub fn vars(&self) -> &[&'static str] {
+        &self.expr.vars
+    }
+}
@@ -0,0 +1,189 @@
+use crate::eval::{Expression, Tester};
+#[cfg(feature = "parsing")]
+use crate::parsing::Tokens;
+#[cfg(feature = "parsing")]
+use alloc::vec::Vec;
+
+/// How the output of a function changes when
+/// a single variable goes from `false` to `true`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Unateness {
+    /// The output never changes, the function does
+    /// not depend on the variable at all
+    Independent,
+    /// The output never goes from `true` to `false`
+    Positive,
+    /// The output never goes from `false` to `true`
+    Negative,
+    /// The output goes both ways, depending
+    /// on the other variables
+    Binate,
+}
+
+/// How a function depends on one of its variables
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Dependency {
+    var: usize,
+    rises: usize,
+    falls: usize,
+    pairs: usize,
+}
+
+impl Dependency {
+    /// The index of the variable
+    pub fn var(&self) -> usize {
+        self.var
+    }
+
+    /// Whether the output depends on the variable at all
+    pub fn is_relevant(&self) -> bool {
+        self.flips() != 0
+    }
+
+    /// The amount of states where flipping the
+    /// variable changes the output
+    pub fn flips(&self) -> usize {
+        // every pair of states counts for both of its states
+        (self.rises + self.falls) * 2
+    }
+
+    /// The fraction of states where flipping the
+    /// variable changes the output
+    pub fn influence(&self) -> f64 {
+        (self.rises + self.falls) as f64 / self.pairs as f64
+    }
+
+    /// Whether the output only ever rises, falls or
+    /// does both as the variable becomes `true`
+    pub fn unateness(&self) -> Unateness {
+        match (self.rises != 0, self.falls != 0) {
+            (false, false) => Unateness::Independent,
+            (true, false) => Unateness::Positive,
+            (false, true) => Unateness::Negative,
+            (true, true) => Unateness::Binate,
+        }
+    }
+}
+
+impl<E: Expression> Tester<E> {
+    /// Find how the output depends on the variable at index `var`
+    pub fn dependency(&self, var: usize) -> Dependency {
+        assert!(var < self.var_count, "Variable out of range");
+
+        let bit = 1 << var;
+        let mut dep = Dependency {
+            var,
+            rises: 0,
+            falls: 0,
+            pairs: 0,
+        };
+        for low in self.iterations().filter(|i| i & bit == 0) {
+            match (self.expr.eval(low), self.expr.eval(low | bit)) {
+                (false, true) => dep.rises += 1,
+                (true, false) => dep.falls += 1,
+                _ => (),
+            }
+            dep.pairs += 1;
+        }
+        dep
+    }
+
+    /// Find how the output depends on each of the variables
+    pub fn dependencies(&self) -> impl Iterator<Item = Dependency> + '_ {
+        (0..self.var_count).map(move |v| self.dependency(v))
+    }
+}
+
+#[cfg(feature = "parsing")]
+impl<'t> Tester<Tokens<'t>> {
+    /// Remove every variable the output does not depend on,
+    /// replacing it with a literal in the expression
+    pub fn drop_irrelevant(&self) -> Self {
+        let irrelevant: Vec<usize> = self
+            .dependencies()
+            .filter(|d| !d.is_relevant())
+            .map(|d| d.var())
+            .collect();
+
+        // go from the last so the remaining indices stay valid
+        let expr = irrelevant
+            .into_iter()
+            .rev()
+            .fold(self.expr.clone(), |expr, v| expr.assign(v, false));
+        Self::with_tokens(expr)
+    }
+}
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::Unateness::{self, *};
+    use crate::eval::Tester;
+    use alloc::vec::Vec;
+
+    /// The unateness of every variable of `src`
+    fn unateness(src: &str) -> Vec<Unateness> {
+        Tester::parse(src)
+            .dependencies()
+            .map(|d| d.unateness())
+            .collect()
+    }
+
+    #[test]
+    fn finds_irrelevant_variables() {
+        let tester = Tester::parse("a | (b & !b)");
+        let deps: Vec<_> = tester.dependencies().collect();
+        assert_eq!(deps.iter().map(|d| d.var()).collect::<Vec<_>>(), [0, 1]);
+        assert!(deps[0].is_relevant());
+        assert!(!deps[1].is_relevant());
+        assert_eq!(deps[1].flips(), 0);
+        assert_eq!(deps[1].influence(), 0.0);
+        assert_eq!(deps[1].unateness(), Independent);
+    }
+
+    #[test]
+    fn drops_irrelevant_variables() {
+        let dropped = Tester::parse("a | (b & !b)").drop_irrelevant();
+        assert_eq!(dropped.vars(), ["a"]);
+        assert_eq!(dropped.truth_table(), Tester::parse("a").truth_table());
+
+        // the variables after a dropped one keep their order
+        let dropped = Tester::parse("a & c | b & !b | d & !d").drop_irrelevant();
+        assert_eq!(dropped.vars(), ["a", "c"]);
+        assert_eq!(dropped.truth_table(), Tester::parse("a & c").truth_table());
+
+        let kept = Tester::parse("a ^ b").drop_irrelevant();
+        assert_eq!(kept.vars(), ["a", "b"]);
+    }
+
+    #[test]
+    fn measures_influence() {
+        let tester = Tester::parse("a ^ b");
+        for dep in tester.dependencies() {
+            assert_eq!(dep.influence(), 1.0);
+            assert_eq!(dep.flips(), 4);
+            assert_eq!(dep.unateness(), Binate);
+        }
+
+        // a only matters when b and c are both true
+        let dep = Tester::parse("a & b & c").dependency(0);
+        assert_eq!(dep.influence(), 0.25);
+        assert_eq!(dep.flips(), 2);
+        // a decides unless b and c are equal
+        let dep = Tester::parse("a & b | !a & c").dependency(0);
+        assert_eq!(dep.influence(), 0.5);
+    }
+
+    #[test]
+    fn finds_unateness() {
+        assert_eq!(unateness("a -> b"), [Negative, Positive]);
+        assert_eq!(unateness("a & !b | c"), [Positive, Negative, Positive]);
+        assert_eq!(unateness("a = b"), [Binate, Binate]);
+        assert_eq!(unateness("a | !a & b & !b"), [Positive, Independent]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Variable out of range")]
+    fn panics_past_the_last_variable() {
+        Tester::parse("a & b").dependency(2);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    eval::{Expression, Tester},
+    parsing::Tokens,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// An assignment of the variables of two expressions
+/// for which they give different results
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Counterexample<'a> {
+    assignment: Vec<(&'a str, bool)>,
+    left: bool,
+    right: bool,
+}
+
+impl<'a> Counterexample<'a> {
+    /// The value of every variable of either expression
+    pub fn assignment(&self) -> &[(&'a str, bool)] {
+        &self.assignment
+    }
+
+    /// The result of the first expression
+    pub fn left(&self) -> bool {
+        self.left
+    }
+
+    /// The result of the second expression
+    pub fn right(&self) -> bool {
+        self.right
+    }
+}
+
+impl<'a> fmt::Display for Counterexample<'a> {
+    /// Writes the assignment as `name=value` pairs
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (n, (name, val)) in self.assignment.iter().enumerate() {
+            if n != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}={}", name, *val as u8)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'t> Tester<Tokens<'t>> {
+    /// Find the first assignment for which the expressions give
+    /// different results, if any. The variables are matched by
+    /// name, those of `self` coming first in the assignment.
+    pub fn counterexample(&self, other: &Tester<Tokens<'t>>) -> Option<Counterexample<'t>> {
+        let mut vars = self.expr.vars().to_vec();
+        for var in other.expr.vars() {
+            if !vars.contains(var) {
+                vars.push(var);
+            }
+        }
+        let left = self.expr.over_vars(&vars);
+        let right = other.expr.over_vars(&vars);
+
+        let union = Tester::<Tokens>::with_tokens(left);
+        union.iterations().find_map(|idx| {
+            let (l, r) = (union.expr.eval(idx), right.eval(idx));
+            if l == r {
+                return None;
+            }
+            Some(Counterexample {
+                assignment: union
+                    .var_vals(idx)
+                    .enumerate()
+                    .map(|(v, val)| (vars[v], val))
+                    .collect(),
+                left: l,
+                right: r,
+            })
+        })
+    }
+
+    /// Whether both expressions give the same result for
+    /// every assignment, matching the variables by name
+    pub fn is_equivalent(&self, other: &Tester<Tokens<'t>>) -> bool {
+        self.counterexample(other).is_none()
+    }
+}
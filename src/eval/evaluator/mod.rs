@@ -21,6 +21,16 @@ impl<E: Expression> Tester<E> {
         }
     }
 
+    /// Test an expression, taking its type as it is, which
+    /// unlike [`Tester::new`] lets the type be inferred, as
+    /// it has to be for expressions built from [combinators]
+    ///
+    /// [`Tester::new`]: `Tester::new`
+    /// [combinators]: `crate::eval::combinators`
+    pub fn with_expr(expr: E, var_count: usize) -> Self {
+        Self { expr, var_count }
+    }
+
     pub fn var_vals<'a, S: State + 'a>(&'a self, state: S) -> impl Iterator<Item = bool> + 'a {
         (0..self.var_count).map(move |v| state.var_at(v))
     }
@@ -29,6 +39,11 @@ impl<E: Expression> Tester<E> {
         self.var_count
     }
 
+    /// The expression being tested
+    pub fn expr(&self) -> &E {
+        &self.expr
+    }
+
     pub fn max_iter(&self) -> usize {
         1 << self.var_count
     }
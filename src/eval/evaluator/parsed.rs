@@ -1,6 +1,6 @@
 use crate::{
     eval::{State, Tester},
-    parsing::{Parser, TokenLiterals, Tokens},
+    parsing::{Notation, ParseError, Parser, TokenLiterals, Tokens},
 };
 
 impl<'t> Tester<Tokens<'t>> {
@@ -20,6 +20,47 @@ impl<'t> Tester<Tokens<'t>> {
         }
     }
 
+    /// Parse an infix expression, returning where and
+    /// why it is malformed instead of panicking
+    pub fn try_parse<'i: 't>(inp: &'i str) -> Result<Self, ParseError> {
+        Parser::parse(inp)
+            .try_shunting_yard()
+            .map(Self::with_tokens)
+    }
+
+    /// Parse an infix expression with the given [`TokenLiterals`],
+    /// returning where and why it is malformed instead of panicking
+    ///
+    /// [`TokenLiterals`]: `TokenLiterals`
+    pub fn try_parse_with_literals<'i: 't>(
+        inp: &'i str,
+        literals: TokenLiterals,
+    ) -> Result<Self, ParseError> {
+        Parser::parse_with_literals(inp, literals)
+            .try_shunting_yard()
+            .map(Self::with_tokens)
+    }
+
+    /// Parse an expression written in the given [`Notation`]
+    ///
+    /// [`Notation`]: `Notation`
+    pub fn parse_notation<'i: 't>(inp: &'i str, notation: Notation) -> Self {
+        Self::with_tokens(Parser::parse(inp).parse_notation(notation))
+    }
+
+    /// Parse an expression written in the given [`Notation`],
+    /// returning where and why it is malformed instead of panicking
+    ///
+    /// [`Notation`]: `Notation`
+    pub fn try_parse_notation<'i: 't>(
+        inp: &'i str,
+        notation: Notation,
+    ) -> Result<Self, ParseError> {
+        Parser::parse(inp)
+            .try_parse_notation(notation)
+            .map(Self::with_tokens)
+    }
+
     pub fn with_tokens(expr: Tokens<'t>) -> Self {
         Self {
             var_count: expr.var_count(),
@@ -0,0 +1,243 @@
+use crate::{
+    eval::TruthTable,
+    parsing::{tree::Node, Token, Tokens},
+};
+use alloc::{collections::btree_set::BTreeSet, vec, vec::Vec};
+
+/// A product term, a conjunction of some of the variables
+/// or their negations.
+///
+/// Every variable whose bit is set in the [`mask`] is left
+/// out of the term, the rest must take the value of their
+/// bit in the [`value`].
+///
+/// [`mask`]: `Implicant::mask`
+/// [`value`]: `Implicant::value`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Implicant {
+    value: usize,
+    mask: usize,
+}
+
+impl Implicant {
+    /// Create a term, any bit of `value` that is also
+    /// in `mask` is ignored
+    pub fn new(value: usize, mask: usize) -> Self {
+        Self {
+            value: value & !mask,
+            mask,
+        }
+    }
+
+    /// The values the variables in the term must take
+    pub fn value(&self) -> usize {
+        self.value
+    }
+
+    /// The variables left out of the term
+    pub fn mask(&self) -> usize {
+        self.mask
+    }
+
+    /// Whether the state `idx` makes the term `true`
+    pub fn covers(&self, idx: usize) -> bool {
+        idx & !self.mask == self.value
+    }
+
+    /// The amount of variables in the term, out of `var_count`
+    pub fn literal_count(&self, var_count: usize) -> usize {
+        var_count - (self.mask & ((1 << var_count) - 1)).count_ones() as usize
+    }
+
+    /// Every state made `true` by the term, out of `var_count` variables
+    pub fn states(&self, var_count: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..1 << var_count).filter(move |&idx| self.covers(idx))
+    }
+
+    /// Render the term as the AND of its variables,
+    /// using the given variable names
+    pub fn to_tokens<'a>(&self, vars: &[&'a str]) -> Tokens<'a> {
+        use Token::*;
+
+        let mut toks = Vec::new();
+        let mut count = 0;
+        for (v, name) in vars.iter().enumerate() {
+            if self.mask & (1 << v) == 0 {
+                toks.push(Var(name, v));
+                if self.value & (1 << v) == 0 {
+                    toks.push(Not);
+                }
+                count += 1;
+            }
+        }
+        for _ in 1..count {
+            toks.push(And);
+        }
+        if toks.is_empty() {
+            toks.push(Literal(true));
+        }
+
+        Tokens::new(toks, vars.to_vec())
+    }
+
+    /// Merge two terms that differ on a single variable
+    fn combine(&self, other: &Self) -> Option<Self> {
+        let diff = self.value ^ other.value;
+        if self.mask == other.mask && diff.count_ones() == 1 {
+            Some(Self::new(self.value, self.mask | diff))
+        } else {
+            None
+        }
+    }
+}
+
+impl TruthTable {
+    /// Find every prime implicant of the function
+    /// using the Quine–McCluskey method
+    pub fn prime_implicants(&self) -> Vec<Implicant> {
+        prime_implicants((0..self.len()).filter(|&idx| self.get(idx)))
+    }
+
+    /// Render the table as the OR of all of its prime implicants
+    /// (its Blake canonical form), using the given variable names
+    pub fn to_tokens<'a>(&self, vars: &[&'a str]) -> Tokens<'a> {
+        assert_eq!(
+            vars.len(),
+            self.var_count(),
+            "A name is needed per variable"
+        );
+
+        let terms = self
+            .prime_implicants()
+            .iter()
+            .map(|term| Node::from_tokens(&term.to_tokens(vars)))
+            .collect();
+        Node::from_chain(Token::Or, terms).to_tokens(vars.to_vec())
+    }
+}
+
+/// Find the prime implicants covering the given states
+pub(crate) fn prime_implicants(states: impl Iterator<Item = usize>) -> Vec<Implicant> {
+    let mut current: BTreeSet<Implicant> = states.map(|idx| Implicant::new(idx, 0)).collect();
+    let mut primes = Vec::new();
+
+    while !current.is_empty() {
+        let terms: Vec<Implicant> = current.into_iter().collect();
+        let mut combined = vec![false; terms.len()];
+        let mut next = BTreeSet::new();
+
+        for i in 0..terms.len() {
+            for j in (i + 1)..terms.len() {
+                if let Some(term) = terms[i].combine(&terms[j]) {
+                    combined[i] = true;
+                    combined[j] = true;
+                    next.insert(term);
+                }
+            }
+        }
+
+        primes.extend(
+            terms
+                .iter()
+                .zip(combined.iter())
+                .filter(|(_, &c)| !c)
+                .map(|(t, _)| *t),
+        );
+        current = next;
+    }
+
+    primes.sort();
+    primes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Implicant;
+    use crate::eval::TruthTable;
+    use alloc::{string::ToString, vec::Vec};
+
+    fn table(var_count: usize, minterms: &[usize]) -> TruthTable {
+        let mut table = TruthTable::new(var_count);
+        for &idx in minterms {
+            table.set(idx, true);
+        }
+        table
+    }
+
+    /// The prime implicants, as `(value, mask)` pairs
+    fn primes(var_count: usize, minterms: &[usize]) -> Vec<(usize, usize)> {
+        table(var_count, minterms)
+            .prime_implicants()
+            .iter()
+            .map(|p| (p.value(), p.mask()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_trivial_primes() {
+        assert_eq!(primes(2, &[]), []);
+        assert_eq!(primes(2, &[0, 1, 2, 3]), [(0, 0b11)]);
+        // XOR has no adjacent minterms to combine
+        assert_eq!(primes(2, &[1, 2]), [(1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn finds_primes_of_majority() {
+        assert_eq!(
+            primes(3, &[3, 5, 6, 7]),
+            [(0b011, 0b100), (0b101, 0b010), (0b110, 0b001)]
+        );
+        let vars = ["a", "b", "c"];
+        assert_eq!(
+            table(3, &[3, 5, 6, 7]).to_tokens(&vars).to_string(),
+            "a & b | a & c | b & c"
+        );
+    }
+
+    #[test]
+    fn finds_primes_of_cyclic_function() {
+        // Σm(0, 1, 2, 5, 6, 7) has six primes of two literals
+        // and no essential ones
+        assert_eq!(
+            primes(3, &[0, 1, 2, 5, 6, 7]),
+            [
+                (0b000, 0b001),
+                (0b000, 0b010),
+                (0b001, 0b100),
+                (0b010, 0b100),
+                (0b101, 0b010),
+                (0b110, 0b001)
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_primes_of_four_variables() {
+        // Σm(0, 1, 2, 5, 6, 7, 8, 9, 10, 14), with the minterms numbered
+        // from `wxyz`: x'y', x'z', yz', w'xz, w'xy and w'y'z
+        assert_eq!(
+            primes(4, &[0, 1, 2, 5, 6, 7, 8, 9, 10, 14]),
+            [
+                (0b0000, 0b1001),
+                (0b0000, 0b1010),
+                (0b0001, 0b0100),
+                (0b0010, 0b1100),
+                (0b0101, 0b0010),
+                (0b0110, 0b0001)
+            ]
+        );
+    }
+
+    #[test]
+    fn describes_implicants() {
+        let term = Implicant::new(0b111, 0b010);
+        assert_eq!(term.value(), 0b101);
+        assert_eq!(term.literal_count(3), 2);
+        assert_eq!(term.states(3).collect::<Vec<_>>(), [0b101, 0b111]);
+        assert_eq!(term.to_tokens(&["a", "b", "c"]).to_string(), "a & c");
+        assert_eq!(
+            Implicant::new(0, 0b11).to_tokens(&["a", "b"]).to_string(),
+            "true"
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use crate::{
+    eval::{implicant::prime_implicants, Implicant, Tester, TruthTable},
+    parsing::Tokens,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+/// A Karnaugh map of a function over 2 to 6 variables
+///
+/// The first half of the variables label the rows, the rest
+/// label the columns, and both axes are in Gray code order,
+/// with the first variable of each axis as the most
+/// significant bit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KarnaughMap<'a> {
+    row_vars: Vec<&'a str>,
+    col_vars: Vec<&'a str>,
+    cells: Vec<bool>,
+    groups: Vec<Implicant>,
+}
+
+impl<'a> KarnaughMap<'a> {
+    /// The smallest amount of variables a map can be made for
+    pub const MIN_VARS: usize = 2;
+    /// The largest amount of variables a map can be made for
+    pub const MAX_VARS: usize = 6;
+
+    /// Create a map of the given table, naming the variables
+    /// with `vars`.
+    ///
+    /// Returns `None` if the table is not over
+    /// 2 to 6 variables.
+    pub fn new(table: &TruthTable, vars: &[&'a str]) -> Option<Self> {
+        let var_count = table.var_count();
+        if !(Self::MIN_VARS..=Self::MAX_VARS).contains(&var_count) {
+            return None;
+        }
+        assert_eq!(
+            vars.len(),
+            var_count,
+            "Expected a name for each variable in the table"
+        );
+
+        let (row_vars, col_vars) = vars.split_at(var_count / 2);
+        let mut map = Self {
+            row_vars: row_vars.to_vec(),
+            col_vars: col_vars.to_vec(),
+            cells: Vec::new(),
+            groups: Vec::new(),
+        };
+        map.cells = (0..map.rows())
+            .flat_map(|r| (0..map.cols()).map(move |c| (r, c)))
+            .map(|(r, c)| table.get(map.state(r, c)))
+            .collect();
+
+        Some(map)
+    }
+
+    /// Also compute the prime implicants of the function,
+    /// so they can be shown as groupings on the map
+    pub fn with_groups(mut self) -> Self {
+        let mut groups = prime_implicants(
+            (0..self.cells.len())
+                .filter(|&i| self.cells[i])
+                .map(|i| self.state(i / self.cols(), i % self.cols())),
+        );
+        // show the largest groups first
+        groups.sort_by_key(|g| (g.literal_count(self.var_count()), *g));
+        self.groups = groups;
+        self
+    }
+
+    /// The amount of variables in the map
+    pub fn var_count(&self) -> usize {
+        self.row_vars.len() + self.col_vars.len()
+    }
+
+    /// The variables labeling the rows
+    pub fn row_vars(&self) -> &[&'a str] {
+        &self.row_vars
+    }
+
+    /// The variables labeling the columns
+    pub fn col_vars(&self) -> &[&'a str] {
+        &self.col_vars
+    }
+
+    /// The amount of rows in the map
+    pub fn rows(&self) -> usize {
+        1 << self.row_vars.len()
+    }
+
+    /// The amount of columns in the map
+    pub fn cols(&self) -> usize {
+        1 << self.col_vars.len()
+    }
+
+    /// The values of the row variables for the given row,
+    /// with the first variable as the most significant bit
+    pub fn row_code(&self, row: usize) -> usize {
+        gray(row)
+    }
+
+    /// The values of the column variables for the given column,
+    /// with the first variable as the most significant bit
+    pub fn col_code(&self, col: usize) -> usize {
+        gray(col)
+    }
+
+    /// The result of the function in the given cell
+    pub fn cell(&self, row: usize, col: usize) -> bool {
+        self.cells[row * self.cols() + col]
+    }
+
+    /// The state, as iterated by a [`Tester`], in the given cell
+    ///
+    /// [`Tester`]: `Tester`
+    pub fn state(&self, row: usize, col: usize) -> usize {
+        let rows = self.row_vars.len();
+        let cols = self.col_vars.len();
+        let (row, col) = (gray(row), gray(col));
+
+        let mut idx = 0;
+        for v in 0..rows {
+            if row & (1 << (rows - 1 - v)) != 0 {
+                idx |= 1 << v;
+            }
+        }
+        for v in 0..cols {
+            if col & (1 << (cols - 1 - v)) != 0 {
+                idx |= 1 << (rows + v);
+            }
+        }
+        idx
+    }
+
+    /// The prime implicant groupings, empty unless the map was
+    /// made [`with_groups`]
+    ///
+    /// [`with_groups`]: `KarnaughMap::with_groups`
+    pub fn groups(&self) -> &[Implicant] {
+        &self.groups
+    }
+
+    /// The groupings covering the given cell
+    pub fn groups_at(&self, row: usize, col: usize) -> impl Iterator<Item = usize> + '_ {
+        let state = self.state(row, col);
+        (0..self.groups.len()).filter(move |&g| self.groups[g].covers(state))
+    }
+
+    /// The term of the given grouping as an expression
+    pub fn group_tokens(&self, group: usize) -> Tokens<'a> {
+        let vars: Vec<&'a str> = self
+            .row_vars
+            .iter()
+            .chain(self.col_vars.iter())
+            .copied()
+            .collect();
+        self.groups[group].to_tokens(&vars)
+    }
+}
+
+impl<'t> Tester<Tokens<'t>> {
+    /// Create a [`KarnaughMap`] of the expression,
+    /// if it has 2 to 6 variables
+    ///
+    /// [`KarnaughMap`]: `KarnaughMap`
+    pub fn karnaugh_map(&self) -> Option<KarnaughMap<'_>> {
+        KarnaughMap::new(&self.truth_table(), self.vars())
+    }
+}
+
+/// The `n`th reflected binary Gray code
+fn gray(n: usize) -> usize {
+    n ^ (n >> 1)
+}
+
+/// Writes the `width` lowest bits of `code`, most significant first
+fn bits(code: usize, width: usize) -> String {
+    (0..width)
+        .rev()
+        .map(|b| if code & (1 << b) != 0 { '1' } else { '0' })
+        .collect()
+}
+
+/// The names of an axis' variables, only separated
+/// when they are not all a single character long
+fn axis_label(vars: &[&str]) -> String {
+    if vars.iter().all(|v| v.chars().count() == 1) {
+        vars.concat()
+    } else {
+        vars.join(",")
+    }
+}
+
+/// The labels of the groupings, when there are few enough of them
+const LABELS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+impl<'a> KarnaughMap<'a> {
+    /// The label of the given grouping, `A` to `Z` then `a` to `z`,
+    /// or the numbers from `1` when there are more groupings
+    /// than letters, so that every grouping has its own label
+    fn group_label(&self, group: usize) -> String {
+        if self.groups.len() <= LABELS.len() {
+            char::from(LABELS[group]).to_string()
+        } else {
+            (group + 1).to_string()
+        }
+    }
+}
+
+impl<'a> fmt::Display for KarnaughMap<'a> {
+    /// Renders the map as plain text, with the grouping
+    /// labels covering each cell after its value,
+    /// followed by the term of each grouping
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let corner = format!(
+            "{} \\ {}",
+            axis_label(&self.row_vars),
+            axis_label(&self.col_vars)
+        );
+
+        // letters follow the value, numbers are separated
+        let sep = if self.groups.len() <= LABELS.len() {
+            ("", "")
+        } else {
+            (" ", ",")
+        };
+        let cell_text = |r, c| -> String {
+            let mut s = String::from(if self.cell(r, c) { "1" } else { "0" });
+            let labels: Vec<String> = self.groups_at(r, c).map(|g| self.group_label(g)).collect();
+            if !labels.is_empty() {
+                s.push_str(sep.0);
+                s.push_str(&labels.join(sep.1));
+            }
+            s
+        };
+        let width = (0..self.rows())
+            .flat_map(|r| (0..self.cols()).map(move |c| (r, c)))
+            .map(|(r, c)| cell_text(r, c).chars().count())
+            .chain(core::iter::once(self.col_vars.len()))
+            .max()
+            .unwrap_or(1);
+        let corner_width = corner.chars().count().max(self.row_vars.len());
+
+        // header
+        write!(f, "{:>w$} |", corner, w = corner_width)?;
+        for c in 0..self.cols() {
+            write!(
+                f,
+                " {:^w$} |",
+                bits(self.col_code(c), self.col_vars.len()),
+                w = width
+            )?;
+        }
+        writeln!(f)?;
+        write!(f, "{:-<w$}-+", "", w = corner_width)?;
+        for _ in 0..self.cols() {
+            write!(f, "-{:-<w$}-+", "", w = width)?;
+        }
+        writeln!(f)?;
+
+        // rows
+        for r in 0..self.rows() {
+            write!(
+                f,
+                "{:>w$} |",
+                bits(self.row_code(r), self.row_vars.len()),
+                w = corner_width
+            )?;
+            for c in 0..self.cols() {
+                write!(f, " {:^w$} |", cell_text(r, c), w = width)?;
+            }
+            writeln!(f)?;
+        }
+
+        // legend
+        for g in 0..self.groups.len() {
+            writeln!(f, "{}: {}", self.group_label(g), self.group_tokens(g))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KarnaughMap;
+    use crate::eval::{Tester, TruthTable};
+    use alloc::{string::ToString, vec::Vec};
+
+    #[test]
+    fn orders_axes_in_gray_code() {
+        let tester = Tester::parse("a & b | c & !d");
+        let map = tester.karnaugh_map().unwrap();
+        assert_eq!(
+            (map.row_vars(), map.col_vars()),
+            (&["a", "b"][..], &["c", "d"][..])
+        );
+        assert_eq!((map.rows(), map.cols()), (4, 4));
+        let codes: Vec<usize> = (0..4).map(|r| map.row_code(r)).collect();
+        assert_eq!(codes, [0b00, 0b01, 0b11, 0b10]);
+        let codes: Vec<usize> = (0..4).map(|c| map.col_code(c)).collect();
+        assert_eq!(codes, [0b00, 0b01, 0b11, 0b10]);
+
+        // a 3 variable map has a single row variable
+        let tester = Tester::parse("a | b & c");
+        let map = tester.karnaugh_map().unwrap();
+        assert_eq!(
+            (map.row_vars(), map.col_vars()),
+            (&["a"][..], &["b", "c"][..])
+        );
+        assert_eq!((map.rows(), map.cols()), (2, 4));
+    }
+
+    #[test]
+    fn maps_cells_to_states() {
+        let tester = Tester::parse("a & b | c & !d");
+        let table = tester.truth_table();
+        let map = tester.karnaugh_map().unwrap();
+        // the first variable of an axis is its most significant bit
+        assert_eq!(map.state(1, 0), 0b0010);
+        assert_eq!(map.state(2, 3), 0b0111);
+        assert_eq!(map.state(3, 1), 0b1001);
+
+        let mut states: Vec<usize> = (0..4)
+            .flat_map(|r| (0..4).map(move |c| (r, c)))
+            .map(|(r, c)| {
+                assert_eq!(map.cell(r, c), table.get(map.state(r, c)));
+                map.state(r, c)
+            })
+            .collect();
+        states.sort_unstable();
+        assert_eq!(states, (0..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn only_maps_2_to_6_variables() {
+        assert!(Tester::parse("a").karnaugh_map().is_none());
+        assert!(Tester::parse("a & b & c & d & e & f & g")
+            .karnaugh_map()
+            .is_none());
+        assert!(Tester::parse("a & b & c & d & e & f")
+            .karnaugh_map()
+            .is_some());
+    }
+
+    #[test]
+    fn groups_prime_implicants() {
+        let tester = Tester::parse("a & b | c & !d");
+        let map = tester.karnaugh_map().unwrap();
+        assert!(map.groups().is_empty());
+
+        let map = map.with_groups();
+        let terms: Vec<_> = (0..map.groups().len())
+            .map(|g| map.group_tokens(g).to_string())
+            .collect();
+        assert_eq!(terms, ["a & b", "c & !d"]);
+        assert_eq!(map.groups_at(2, 3).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(map.groups_at(0, 0).count(), 0);
+    }
+
+    #[test]
+    fn displays_a_4_variable_map() {
+        let tester = Tester::parse("a & b & !c | !a & !b | b & d");
+        let map = tester.karnaugh_map().unwrap().with_groups();
+        assert_eq!(
+            map.to_string(),
+            "\
+ab \\ cd | 00  | 01  | 11  | 10  |
+--------+-----+-----+-----+-----+
+     00 | 1A  | 1AB | 1AB | 1A  |
+     01 |  0  | 1BC | 1BC |  0  |
+     11 | 1D  | 1CD | 1C  |  0  |
+     10 |  0  |  0  |  0  |  0  |
+A: !a & !b
+B: !a & d
+C: b & d
+D: a & b & !c
+"
+        );
+    }
+
+    #[test]
+    fn labels_every_group() {
+        // 2 to 4 of the variables, with a group
+        // for every 2 that are set and 2 that are not
+        let mut table = TruthTable::new(6);
+        for state in 0..64usize {
+            table.set(state, (2..=4).contains(&state.count_ones()));
+        }
+        let map = KarnaughMap::new(&table, &["a", "b", "c", "d", "e", "f"])
+            .unwrap()
+            .with_groups();
+        assert_eq!(map.groups().len(), 90);
+
+        let text = map.to_string();
+        assert!(text.contains(" 1 1,2,3,4,5,6 "), "{}", text);
+        assert!(text.contains("\n90: "), "{}", text);
+        assert!(!text.contains('?'));
+    }
+}
@@ -1,4 +1,4 @@
-mod state;
+pub(crate) mod state;
 pub use state::State;
 
 mod evaluator;
@@ -6,3 +6,71 @@ pub use evaluator::Tester;
 
 mod expr;
 pub use expr::Expression;
+
+pub mod combinators;
+
+#[cfg(feature = "macros")]
+mod compiled;
+#[cfg(feature = "macros")]
+pub use compiled::Compiled;
+
+#[cfg(feature = "parsing")]
+mod table;
+#[cfg(feature = "parsing")]
+pub use table::TruthTable;
+
+#[cfg(feature = "parsing")]
+mod equiv;
+#[cfg(feature = "parsing")]
+pub use equiv::Counterexample;
+
+#[cfg(feature = "parsing")]
+mod assert;
+#[cfg(feature = "parsing")]
+pub use assert::Assignment;
+#[cfg(feature = "parsing")]
+#[doc(hidden)]
+pub use assert::Message;
+
+#[cfg(feature = "parsing")]
+mod trace;
+#[cfg(feature = "parsing")]
+pub use trace::{Subexpr, Trace};
+
+#[cfg(feature = "parsing")]
+mod batch;
+#[cfg(feature = "parsing")]
+#[doc(hidden)]
+pub use batch::split_word;
+#[cfg(feature = "parsing")]
+pub use batch::{Batch, BatchError, BatchErrorKind, Expectation, Failure, Record, Report, Verdict};
+
+#[cfg(feature = "parsing")]
+mod rule;
+#[cfg(feature = "parsing")]
+pub use rule::RuleError;
+
+#[cfg(feature = "parsing")]
+mod anf;
+#[cfg(feature = "parsing")]
+pub use anf::Anf;
+
+#[cfg(feature = "parsing")]
+pub(crate) mod implicant;
+#[cfg(feature = "parsing")]
+pub use implicant::Implicant;
+
+#[cfg(feature = "parsing")]
+mod kmap;
+#[cfg(feature = "parsing")]
+pub use kmap::KarnaughMap;
+
+mod dependency;
+pub use dependency::{Dependency, Unateness};
+
+#[cfg(feature = "parsing")]
+mod post;
+#[cfg(feature = "parsing")]
+pub use post::{
+    blocking_classes, blocking_operators, is_functionally_complete, PostClass, PostClasses,
+};
@@ -0,0 +1,253 @@
+use crate::{
+    eval::{Anf, TruthTable},
+    parsing::Token,
+};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// The five maximal classes of Post's lattice.
+///
+/// A set of functions is functionally complete if, and only
+/// if, none of these classes contains all of them.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum PostClass {
+    /// The functions where `f(false, …, false) = false`
+    ZeroPreserving,
+    /// The functions where `f(true, …, true) = true`
+    OnePreserving,
+    /// The functions where `f(¬x₁, …, ¬xₙ) = ¬f(x₁, …, xₙ)`
+    SelfDual,
+    /// The functions that never fall when an input rises
+    Monotone,
+    /// The functions that are an XOR of variables and constants
+    Affine,
+}
+
+impl PostClass {
+    /// Every maximal class, in order
+    pub const ALL: [PostClass; 5] = [
+        PostClass::ZeroPreserving,
+        PostClass::OnePreserving,
+        PostClass::SelfDual,
+        PostClass::Monotone,
+        PostClass::Affine,
+    ];
+}
+
+impl fmt::Display for PostClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            PostClass::ZeroPreserving => "0-preserving",
+            PostClass::OnePreserving => "1-preserving",
+            PostClass::SelfDual => "self-dual",
+            PostClass::Monotone => "monotone",
+            PostClass::Affine => "affine",
+        })
+    }
+}
+
+/// The classification of a single function
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct PostClasses {
+    zero_preserving: bool,
+    one_preserving: bool,
+    self_dual: bool,
+    monotone: bool,
+    affine: bool,
+    symmetric: bool,
+}
+
+impl PostClasses {
+    /// Whether the function is in the given class
+    pub fn contains(&self, class: PostClass) -> bool {
+        match class {
+            PostClass::ZeroPreserving => self.zero_preserving,
+            PostClass::OnePreserving => self.one_preserving,
+            PostClass::SelfDual => self.self_dual,
+            PostClass::Monotone => self.monotone,
+            PostClass::Affine => self.affine,
+        }
+    }
+
+    /// Whether `f(false, …, false) = false`
+    pub fn is_zero_preserving(&self) -> bool {
+        self.zero_preserving
+    }
+
+    /// Whether `f(true, …, true) = true`
+    pub fn is_one_preserving(&self) -> bool {
+        self.one_preserving
+    }
+
+    /// Whether `f(¬x₁, …, ¬xₙ) = ¬f(x₁, …, xₙ)`
+    pub fn is_self_dual(&self) -> bool {
+        self.self_dual
+    }
+
+    /// Whether the function never falls when an input rises
+    pub fn is_monotone(&self) -> bool {
+        self.monotone
+    }
+
+    /// Whether the function is an XOR of variables and constants
+    pub fn is_affine(&self) -> bool {
+        self.affine
+    }
+
+    /// Whether the output only depends on how
+    /// many of the inputs are `true`
+    pub fn is_symmetric(&self) -> bool {
+        self.symmetric
+    }
+}
+
+impl TruthTable {
+    /// Classify the function against Post's lattice
+    pub fn post_classes(&self) -> PostClasses {
+        let last = self.len() - 1;
+
+        let self_dual = (0..self.len()).all(|idx| self.get(idx) != self.get(idx ^ last));
+        let monotone = (0..self.var_count()).all(|v| {
+            let bit = 1 << v;
+            (0..self.len())
+                .filter(|idx| idx & bit == 0)
+                .all(|idx| !self.get(idx) || self.get(idx | bit))
+        });
+        let symmetric = {
+            // the output of the first state seen with each weight
+            let mut seen: Vec<Option<bool>> = vec![None; self.var_count() + 1];
+            (0..self.len()).all(|idx| {
+                let weight = &mut seen[idx.count_ones() as usize];
+                *weight.get_or_insert(self.get(idx)) == self.get(idx)
+            })
+        };
+
+        PostClasses {
+            zero_preserving: !self.get(0),
+            one_preserving: self.get(last),
+            self_dual,
+            monotone,
+            affine: Anf::from_table(self).degree() <= 1,
+            symmetric,
+        }
+    }
+
+    /// The function computed by an operator, or a literal, as
+    /// a gate. Variables are the identity function.
+    ///
+    /// Returns `None` for parenthesis.
+    pub fn from_token(token: Token) -> Option<Self> {
+        let (var_count, f): (usize, fn(bool, bool) -> bool) = match token {
+            Token::Literal(true) => (0, |_, _| true),
+            Token::Literal(false) => (0, |_, _| false),
+            Token::Var(..) => (1, |a, _| a),
+            Token::Not => (1, |a, _| !a),
+            Token::And => (2, |a, b| a && b),
+            Token::Xor => (2, |a, b| a ^ b),
+            Token::Or => (2, |a, b| a || b),
+            Token::Implication => (2, |a, b| !a || b),
+            Token::Equality => (2, |a, b| a == b),
+            Token::LParen | Token::RParen => return None,
+        };
+
+        let mut table = Self::new(var_count);
+        for idx in 0..table.len() {
+            table.set(idx, f(idx & 1 != 0, idx & 2 != 0));
+        }
+        Some(table)
+    }
+}
+
+/// The maximal classes of Post's lattice that contain every
+/// one of the given functions, and so keep them from being
+/// functionally complete
+pub fn blocking_classes<'t>(gates: impl IntoIterator<Item = &'t TruthTable>) -> Vec<PostClass> {
+    let classes: Vec<PostClasses> = gates.into_iter().map(|g| g.post_classes()).collect();
+    PostClass::ALL
+        .iter()
+        .copied()
+        .filter(|&class| classes.iter().all(|c| c.contains(class)))
+        .collect()
+}
+
+/// Whether every function can be built out of the given ones,
+/// as per Post's functional completeness theorem
+pub fn is_functionally_complete<'t>(gates: impl IntoIterator<Item = &'t TruthTable>) -> bool {
+    blocking_classes(gates).is_empty()
+}
+
+/// The maximal classes of Post's lattice that keep the given
+/// operators from being functionally complete.
+///
+/// Parenthesis are ignored.
+pub fn blocking_operators(ops: &[Token]) -> Vec<PostClass> {
+    let gates: Vec<TruthTable> = ops
+        .iter()
+        .filter_map(|&t| TruthTable::from_token(t))
+        .collect();
+    blocking_classes(&gates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blocking_operators, PostClass, PostClass::*};
+    use crate::{eval::TruthTable, parsing::Token};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn classifies_basic_connectives() {
+        // 0-preserving, 1-preserving, self-dual, monotone, affine, symmetric
+        let cases = [
+            (
+                Token::Literal(false),
+                [true, false, false, true, true, true],
+            ),
+            (Token::Literal(true), [false, true, false, true, true, true]),
+            (Token::Var("a", 0), [true, true, true, true, true, true]),
+            (Token::Not, [false, false, true, false, true, true]),
+            (Token::And, [true, true, false, true, false, true]),
+            (Token::Or, [true, true, false, true, false, true]),
+            (Token::Xor, [true, false, false, false, true, true]),
+            (
+                Token::Implication,
+                [false, true, false, false, false, false],
+            ),
+            (Token::Equality, [false, true, false, false, true, true]),
+        ];
+        for (token, expected) in cases.iter() {
+            let c = TruthTable::from_token(*token)
+                .expect("Not a parenthesis")
+                .post_classes();
+            let actual = [
+                c.is_zero_preserving(),
+                c.is_one_preserving(),
+                c.is_self_dual(),
+                c.is_monotone(),
+                c.is_affine(),
+                c.is_symmetric(),
+            ];
+            assert_eq!(actual, *expected, "{:?}", token);
+        }
+    }
+
+    #[test]
+    fn finds_blocking_classes() {
+        let none: Vec<_> = Vec::new();
+        assert_eq!(
+            blocking_operators(&[Token::And, Token::Or]),
+            [ZeroPreserving, OnePreserving, Monotone]
+        );
+        assert_eq!(blocking_operators(&[Token::Xor, Token::Equality]), [Affine]);
+        assert_eq!(blocking_operators(&[Token::Implication]), [OnePreserving]);
+        assert_eq!(blocking_operators(&[Token::Not, Token::And]), none);
+        assert_eq!(
+            blocking_operators(&[Token::Implication, Token::Literal(false)]),
+            none
+        );
+        // nothing can be built out of parenthesis alone
+        assert_eq!(
+            blocking_operators(&[Token::LParen, Token::RParen]),
+            PostClass::ALL
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use crate::eval::{Expression, Tester, TruthTable};
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+
+/// Why a rule number could not be read
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum RuleError {
+    /// There were no digits
+    Empty,
+    /// A character that is not a digit of the base
+    InvalidDigit(char),
+    /// The number has more bits than there are states
+    TooLarge,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleError::Empty => f.write_str("no digits"),
+            RuleError::InvalidDigit(c) => write!(f, "`{}` is not a digit", c),
+            RuleError::TooLarge => f.write_str("more bits than states"),
+        }
+    }
+}
+
+impl TruthTable {
+    /// The table as a hexadecimal rule number, where bit `i`
+    /// is the result for the state `i`. There is a digit for
+    /// every four states, so leading zeros are kept.
+    pub fn to_hex(&self) -> String {
+        let digits = self.len().div_ceil(4);
+        (0..digits)
+            .rev()
+            .map(|d| {
+                let nibble = (self.words()[d / 16] >> (d % 16 * 4)) & 0xF;
+                core::char::from_digit(nibble as u32, 16).unwrap()
+            })
+            .collect()
+    }
+
+    /// The table as a decimal rule number, where bit `i`
+    /// is the result for the state `i`
+    pub fn to_decimal(&self) -> String {
+        // the largest power of ten that fits in a u64
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut words = self.words().to_vec();
+        let mut chunks = Vec::new();
+        while words.iter().any(|&w| w != 0) {
+            // long division by CHUNK, from the most significant word
+            let mut rem = 0u128;
+            for w in words.iter_mut().rev() {
+                let cur = rem << 64 | *w as u128;
+                *w = (cur / CHUNK as u128) as u64;
+                rem = cur % CHUNK as u128;
+            }
+            chunks.push(rem as u64);
+        }
+
+        match chunks.pop() {
+            None => "0".into(),
+            Some(first) => {
+                let mut out = format!("{}", first);
+                for chunk in chunks.iter().rev() {
+                    out.push_str(&format!("{:019}", chunk));
+                }
+                out
+            }
+        }
+    }
+
+    /// Read a table over `var_count` variables from a hexadecimal
+    /// rule number, with or without a leading `0x`. Underscores
+    /// between digits are ignored.
+    pub fn from_hex(var_count: usize, hex: &str) -> Result<Self, RuleError> {
+        let hex = hex.trim();
+        let hex = hex
+            .strip_prefix("0x")
+            .or_else(|| hex.strip_prefix("0X"))
+            .unwrap_or(hex);
+
+        let mut table = Self::new(var_count);
+        let mut bit = 0;
+        let mut empty = true;
+        for c in hex.chars().rev().filter(|&c| c != '_') {
+            let nibble = c.to_digit(16).ok_or(RuleError::InvalidDigit(c))? as u64;
+            empty = false;
+            if nibble == 0 {
+                bit += 4;
+                continue;
+            }
+            if bit + (64 - nibble.leading_zeros() as usize) > table.len() {
+                return Err(RuleError::TooLarge);
+            }
+            table.words_mut()[bit / 64] |= nibble << (bit % 64);
+            bit += 4;
+        }
+
+        if empty {
+            Err(RuleError::Empty)
+        } else {
+            Ok(table)
+        }
+    }
+
+    /// Read a table over `var_count` variables from a decimal
+    /// rule number. Underscores between digits are ignored.
+    pub fn from_decimal(var_count: usize, dec: &str) -> Result<Self, RuleError> {
+        let mut table = Self::new(var_count);
+        let len = table.len();
+        let mut empty = true;
+        for c in dec.trim().chars().filter(|&c| c != '_') {
+            let digit = c.to_digit(10).ok_or(RuleError::InvalidDigit(c))? as u128;
+            empty = false;
+
+            // multiply by ten and add the digit, from the least
+            // significant word, carrying into the next one
+            let mut carry = digit;
+            for w in table.words_mut() {
+                let cur = *w as u128 * 10 + carry;
+                *w = cur as u64;
+                carry = cur >> 64;
+            }
+            let overflow = len < 64 && table.words()[0] >> len != 0;
+            if carry != 0 || overflow {
+                return Err(RuleError::TooLarge);
+            }
+        }
+
+        if empty {
+            Err(RuleError::Empty)
+        } else {
+            Ok(table)
+        }
+    }
+}
+
+impl<E: Expression> Tester<E> {
+    /// The output column of the expression as a hexadecimal
+    /// rule number, see [`TruthTable::to_hex`]
+    ///
+    /// [`TruthTable::to_hex`]: `TruthTable::to_hex`
+    pub fn rule_hex(&self) -> String {
+        self.truth_table().to_hex()
+    }
+
+    /// The output column of the expression as a decimal
+    /// rule number, see [`TruthTable::to_decimal`]
+    ///
+    /// [`TruthTable::to_decimal`]: `TruthTable::to_decimal`
+    pub fn rule_number(&self) -> String {
+        self.truth_table().to_decimal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RuleError;
+    use crate::{
+        eval::{Tester, TruthTable},
+        parsing::{Generator, SplitMix64},
+    };
+    use alloc::{format, string::ToString};
+
+    fn table(src: &str) -> TruthTable {
+        Tester::parse(src).truth_table()
+    }
+
+    fn assert_round_trips(table: &TruthTable) {
+        let n = table.var_count();
+        assert_eq!(TruthTable::from_hex(n, &table.to_hex()).as_ref(), Ok(table));
+        assert_eq!(
+            TruthTable::from_decimal(n, &table.to_decimal()).as_ref(),
+            Ok(table)
+        );
+    }
+
+    #[test]
+    fn numbers_known_rules() {
+        assert_eq!(table("a & b").to_decimal(), "8");
+        assert_eq!(table("a ^ b").to_decimal(), "6");
+        assert_eq!(table("a | b").to_hex(), "e");
+        assert_eq!(table("a -> b").to_decimal(), "13");
+        assert_eq!(table("a & b | a & c | b & c").to_decimal(), "232");
+        assert_eq!(table("a & b | a & c | b & c").to_hex(), "e8");
+        // every state is a digit, so leading zeros are kept
+        assert_eq!(table("a & b & c & d & e").to_hex(), "80000000");
+        assert_eq!(table("a & b & c & d & e & f & g").to_hex().len(), 32);
+        assert_eq!(
+            table("a | !a | b & c & d & e & f").to_decimal(),
+            "18446744073709551615"
+        );
+        assert_eq!(
+            table("a & b & c & d & e & f & g").to_decimal(),
+            "170141183460469231731687303715884105728"
+        );
+    }
+
+    #[test]
+    fn round_trips_every_small_table() {
+        for var_count in 0..=2 {
+            let len = 1 << var_count;
+            for rule in 0..(1u64 << len) {
+                let mut table = TruthTable::new(var_count);
+                for idx in 0..len {
+                    table.set(idx, rule & (1 << idx) != 0);
+                }
+                assert_eq!(table.to_decimal(), rule.to_string());
+                assert_eq!(table.to_hex(), format!("{:x}", rule));
+                assert_round_trips(&table);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_larger_tables() {
+        for var_count in 3..=8 {
+            let gen = Generator::new(var_count);
+            for seed in 0..20 {
+                let tokens = gen.generate(SplitMix64::new(seed));
+                let table = Tester::with_tokens(tokens).truth_table();
+                assert_eq!(table.to_hex().len(), (1 << var_count) / 4);
+                assert_round_trips(&table);
+            }
+        }
+    }
+
+    #[test]
+    fn reads_prefixes_and_underscores() {
+        let majority = table("a & b | a & c | b & c");
+        assert_eq!(TruthTable::from_hex(3, "0xE8"), Ok(majority.clone()));
+        assert_eq!(TruthTable::from_hex(3, " 0X0_e8 "), Ok(majority.clone()));
+        assert_eq!(TruthTable::from_hex(3, "00000e8"), Ok(majority.clone()));
+        assert_eq!(TruthTable::from_decimal(3, "2_32"), Ok(majority));
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        assert_eq!(TruthTable::from_hex(2, "10"), Err(RuleError::TooLarge));
+        assert_eq!(TruthTable::from_hex(3, "1ff"), Err(RuleError::TooLarge));
+        assert_eq!(TruthTable::from_hex(0, "2"), Err(RuleError::TooLarge));
+        assert_eq!(TruthTable::from_decimal(2, "16"), Err(RuleError::TooLarge));
+        assert_eq!(
+            TruthTable::from_decimal(6, "18446744073709551616"),
+            Err(RuleError::TooLarge)
+        );
+
+        assert_eq!(
+            TruthTable::from_hex(2, "g"),
+            Err(RuleError::InvalidDigit('g'))
+        );
+        assert_eq!(
+            TruthTable::from_hex(2, "-1"),
+            Err(RuleError::InvalidDigit('-'))
+        );
+        assert_eq!(
+            TruthTable::from_decimal(2, "1a"),
+            Err(RuleError::InvalidDigit('a'))
+        );
+
+        assert_eq!(TruthTable::from_hex(2, "0x"), Err(RuleError::Empty));
+        assert_eq!(TruthTable::from_decimal(2, " _ "), Err(RuleError::Empty));
+    }
+}
@@ -15,6 +15,13 @@ macro_rules! state_num_impl {
     )*)
 }
 
+/// The index of the given state, as iterated by a `Tester`,
+/// when only the first `var_count` variables are considered
+#[cfg(feature = "parsing")]
+pub(crate) fn state_index<S: State>(state: &S, var_count: usize) -> usize {
+    (0..var_count).fold(0, |idx, v| idx | (state.var_at(v) as usize) << v)
+}
+
 state_num_impl! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
 
 impl State for &[bool] {
@@ -24,9 +31,9 @@ impl State for &[bool] {
     }
 }
 
-#[cfg(feature = "parsing")]
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
-#[cfg(feature = "parsing")]
+#[cfg(feature = "alloc")]
 impl State for Vec<bool> {
     #[inline]
     fn var_at(&self, idx: usize) -> bool {
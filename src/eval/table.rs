@@ -0,0 +1,133 @@
+use crate::eval::{state::state_index, Expression, State, Tester};
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The packed output column of an [`Expression`]
+///
+/// Bit `i` holds the result of the expression for
+/// the `i`th state, as iterated by a [`Tester`].
+///
+/// [`Expression`]: `Expression`
+/// [`Tester`]: `Tester`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "Table")
+)]
+pub struct TruthTable {
+    var_count: usize,
+    bits: Vec<u64>,
+}
+
+impl TruthTable {
+    /// Create a table for `var_count` variables
+    /// where every state is `false`
+    pub fn new(var_count: usize) -> Self {
+        Self {
+            var_count,
+            bits: vec![0; Self::word_count(var_count)],
+        }
+    }
+
+    /// The amount of variables the table is over
+    pub fn var_count(&self) -> usize {
+        self.var_count
+    }
+
+    /// The amount of states (rows) in the table, which is
+    /// never `0`, as even `0` variables make a single state
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        1 << self.var_count
+    }
+
+    /// The result of the expression for the state `idx`
+    pub fn get(&self, idx: usize) -> bool {
+        self.bits[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    /// Set the result of the expression for the state `idx`
+    pub fn set(&mut self, idx: usize, val: bool) {
+        if val {
+            self.bits[idx / 64] |= 1 << (idx % 64);
+        } else {
+            self.bits[idx / 64] &= !(1 << (idx % 64));
+        }
+    }
+
+    /// The number of states for which the expression is `true`
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The packed table, 64 states per word, with the
+    /// first state in the least significant bit
+    pub fn words(&self) -> &[u64] {
+        &self.bits
+    }
+
+    pub(crate) fn words_mut(&mut self) -> &mut [u64] {
+        &mut self.bits
+    }
+
+    fn word_count(var_count: usize) -> usize {
+        (1usize << var_count).div_ceil(64)
+    }
+}
+
+/// The serialized form of a [`TruthTable`], checked before it is used
+///
+/// [`TruthTable`]: `TruthTable`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct Table {
+    var_count: usize,
+    bits: Vec<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Table> for TruthTable {
+    type Error = &'static str;
+
+    fn try_from(table: Table) -> Result<Self, Self::Error> {
+        // the amount of states must fit in a `usize`
+        if table.var_count >= usize::BITS as usize {
+            return Err("`var_count` is too large");
+        }
+        if table.bits.len() != Self::word_count(table.var_count) {
+            return Err("`bits` does not have a word per 64 states");
+        }
+        // a table of less than 64 states leaves the last bits unset
+        if table.var_count < 6 && table.bits[0] >> (1 << table.var_count) != 0 {
+            return Err("`bits` has bits set past the last state");
+        }
+
+        Ok(Self {
+            var_count: table.var_count,
+            bits: table.bits,
+        })
+    }
+}
+
+impl Expression for TruthTable {
+    fn eval<S: State>(&self, state: S) -> bool {
+        self.get(state_index(&state, self.var_count))
+    }
+}
+
+impl<E: Expression> Tester<E> {
+    /// Evaluate every state, packing the results into a [`TruthTable`]
+    ///
+    /// [`TruthTable`]: `TruthTable`
+    pub fn truth_table(&self) -> TruthTable {
+        let mut table = TruthTable::new(self.var_count);
+        for idx in self.iterations() {
+            table.set(idx, self.expr.eval(idx));
+        }
+        table
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    eval::State,
+    parsing::{ParseError, Parser, Token, TokenLiterals},
+};
+use alloc::{format, string::String, vec::Vec};
+use core::{fmt, ops::Range};
+
+/// A part of an expression, along with the value it had
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Subexpr<'i> {
+    token: Token<'i>,
+    text: &'i str,
+    span: Range<usize>,
+    /// the source text of the token itself
+    spelled: &'i str,
+    value: bool,
+}
+
+impl<'i> Subexpr<'i> {
+    /// The value or the outermost operator
+    pub fn token(&self) -> Token<'i> {
+        self.token
+    }
+
+    /// Whether this is an operator applied to subexpressions,
+    /// rather than a variable or a literal
+    pub fn is_operator(&self) -> bool {
+        self.token.arity() != 0
+    }
+
+    /// The source text of the whole subexpression, without
+    /// the parenthesis around it, if it is in any
+    pub fn text(&self) -> &'i str {
+        self.text
+    }
+
+    /// Where the subexpression is in the source, in bytes
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The value the subexpression had
+    pub fn value(&self) -> bool {
+        self.value
+    }
+}
+
+/// The value of every part of an expression for a single state,
+/// to find which of them made the expression true or false
+///
+/// Displayed, it annotates the expression with the value of
+/// every variable and subexpression, like
+/// `(a=1 & b=0)=0 | c=0 → 0`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Trace<'i> {
+    steps: Vec<Subexpr<'i>>,
+}
+
+impl<'i> Trace<'i> {
+    /// Evaluate an infix expression for the given state, with
+    /// its variables indexed by the order in which they first
+    /// appear, like [`Tester::parse`] does
+    ///
+    /// [`Tester::parse`]: `crate::eval::Tester::parse`
+    pub fn new<S: State>(source: &'i str, state: S) -> Result<Self, ParseError> {
+        Self::with_literals(source, TokenLiterals::default(), state)
+    }
+
+    /// Evaluate an infix expression written with
+    /// the given [`TokenLiterals`] for the given state
+    ///
+    /// [`TokenLiterals`]: `TokenLiterals`
+    pub fn with_literals<S: State>(
+        source: &'i str,
+        literals: TokenLiterals,
+        state: S,
+    ) -> Result<Self, ParseError> {
+        let (toks, spans) =
+            Parser::parse_with_literals(source, literals).try_shunting_yard_spans()?;
+
+        let mut spans = spans.into_iter();
+        let mut steps = Vec::new();
+        toks.fold(|t, args| {
+            let value = match (t, args.as_slice()) {
+                (Token::Var(_, v), _) => state.var_at(v),
+                (Token::Literal(v), _) => v,
+                (Token::Not, [v]) => !v,
+                (Token::And, [a, b]) => *a && *b,
+                (Token::Xor, [a, b]) => a ^ b,
+                (Token::Or, [a, b]) => *a || *b,
+                (Token::Implication, [a, b]) => !a || *b,
+                (Token::Equality, [a, b]) => a == b,
+                _ => unreachable!("Only values and operators are parsed"),
+            };
+            let (span, at) = spans.next().expect("Every token has a span");
+            steps.push(Subexpr {
+                token: t,
+                text: &source[span.clone()],
+                span,
+                spelled: &source[at],
+                value,
+            });
+            value
+        });
+        Ok(Self { steps })
+    }
+
+    /// Every value and operator of the expression, with its
+    /// operands before it, in Reverse Polish Notation
+    pub fn steps(&self) -> &[Subexpr<'i>] {
+        &self.steps
+    }
+
+    /// Every operator of the expression,
+    /// the outermost of them last
+    pub fn operators(&self) -> impl Iterator<Item = &Subexpr<'i>> + '_ {
+        self.steps.iter().filter(|s| s.is_operator())
+    }
+
+    /// The value of the whole expression
+    pub fn value(&self) -> bool {
+        self.steps
+            .last()
+            .expect("An expression has at least one value")
+            .value
+    }
+}
+
+impl<'i> fmt::Display for Trace<'i> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let last = self.steps.len() - 1;
+        let mut stack: Vec<String> = Vec::new();
+        for (n, s) in self.steps.iter().enumerate() {
+            let op = s.spelled;
+            let inner = match s.token {
+                Token::Var(..) => format!("{}={}", op, s.value as u8),
+                Token::Literal(_) => op.into(),
+                Token::Not => {
+                    let v = stack.pop().expect("Not has one operand");
+                    // words like `not` need a space after them
+                    match op.chars().last() {
+                        Some(c) if c.is_alphanumeric() => format!("{} {}", op, v),
+                        _ => format!("{}{}", op, v),
+                    }
+                }
+                _ => {
+                    let r = stack.pop().expect("Binary operators have two operands");
+                    let l = stack.pop().expect("Binary operators have two operands");
+                    format!("{} {} {}", l, op, r)
+                }
+            };
+            stack.push(if !s.is_operator() || n == last {
+                inner
+            } else {
+                format!("({})={}", inner, s.value as u8)
+            });
+        }
+
+        let out = stack.pop().expect("An expression has at least one value");
+        write!(f, "{} → {}", out, self.value() as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trace;
+    use crate::parsing::{ParseErrorKind, Token};
+    use alloc::{string::ToString, vec::Vec};
+
+    /// The text, span and value of every step
+    fn steps<'i>(trace: &Trace<'i>) -> Vec<(&'i str, core::ops::Range<usize>, bool)> {
+        trace
+            .steps()
+            .iter()
+            .map(|s| (s.text(), s.span(), s.value()))
+            .collect()
+    }
+
+    #[test]
+    fn displays_every_value() {
+        let trace = Trace::new("(a & b) | c", 0b001u8).unwrap();
+        assert_eq!(trace.to_string(), "(a=1 & b=0)=0 | c=0 → 0");
+        assert!(!trace.value());
+
+        let trace = Trace::new("a -> !b", 0b11u8).unwrap();
+        assert_eq!(trace.to_string(), "a=1 -> (!b=1)=0 → 0");
+    }
+
+    #[test]
+    fn spans_leave_out_parens() {
+        let trace = Trace::new("(a & b) | c", 0b001u8).unwrap();
+        assert_eq!(
+            steps(&trace),
+            [
+                ("a", 1..2, true),
+                ("b", 5..6, false),
+                ("a & b", 1..6, false),
+                ("c", 10..11, false),
+                ("(a & b) | c", 0..11, false),
+            ]
+        );
+
+        let trace = Trace::new("!((a | b))", 0u8).unwrap();
+        assert_eq!(
+            steps(&trace),
+            [
+                ("a", 3..4, false),
+                ("b", 7..8, false),
+                ("a | b", 3..8, false),
+                ("!((a | b))", 0..10, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn operators_come_outermost_last() {
+        let trace = Trace::new("a & b | c", 0b111u8).unwrap();
+        let ops: Vec<Token> = trace.operators().map(|s| s.token()).collect();
+        assert_eq!(ops, [Token::And, Token::Or]);
+        assert!(trace.value());
+    }
+
+    #[test]
+    fn spells_word_operators() {
+        let trace = Trace::new("not a and b", 0b10u8).unwrap();
+        assert_eq!(trace.to_string(), "(not a=0)=1 and b=1 → 1");
+        assert_eq!(trace.steps()[1].text(), "not a");
+
+        let trace = Trace::new("¬a ∨ b", 0b01u8).unwrap();
+        assert_eq!(trace.to_string(), "(¬a=1)=0 ∨ b=0 → 0");
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let err = Trace::new("a &", 0u8).unwrap_err();
+        assert_eq!(
+            (err.offset(), err.kind()),
+            (3, ParseErrorKind::MissingOperand)
+        );
+        let err = Trace::new("(a | b", 0u8).unwrap_err();
+        assert_eq!(
+            (err.offset(), err.kind()),
+            (0, ParseErrorKind::UnclosedParen)
+        );
+        let err = Trace::new("a b", 0u8).unwrap_err();
+        assert_eq!(
+            (err.offset(), err.kind()),
+            (2, ParseErrorKind::MissingOperator)
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use crate::eval::{Expression, Tester};
+#[cfg(feature = "parsing")]
+use crate::parsing::Tokens;
+use core::fmt::{self, Write};
+
+/// How the values of the variables and
+/// the result are written out
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ValueStyle {
+    /// `0` and `1`
+    Digits,
+    /// `false` and `true`
+    Words,
+    /// `F` and `T`
+    Letters,
+}
+
+impl ValueStyle {
+    /// The spelling of the given value
+    pub fn spell(&self, val: bool) -> &'static str {
+        match (self, val) {
+            (ValueStyle::Digits, false) => "0",
+            (ValueStyle::Digits, true) => "1",
+            (ValueStyle::Words, false) => "false",
+            (ValueStyle::Words, true) => "true",
+            (ValueStyle::Letters, false) => "F",
+            (ValueStyle::Letters, true) => "T",
+        }
+    }
+}
+
+/// Which states of the truth table are written out
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Rows {
+    /// Every state, like [`Tester::eval`]
+    ///
+    /// [`Tester::eval`]: `Tester::eval`
+    All,
+    /// Only the states where the expression is `true`,
+    /// like [`Tester::successes`]
+    ///
+    /// [`Tester::successes`]: `Tester::successes`
+    Successes,
+    /// Only the states where the expression is `false`,
+    /// like [`Tester::failures`]
+    ///
+    /// [`Tester::failures`]: `Tester::failures`
+    Failures,
+}
+
+impl Rows {
+    /// Whether a state with the given result is written out
+    pub fn includes(&self, res: bool) -> bool {
+        match self {
+            Rows::All => true,
+            Rows::Successes => res,
+            Rows::Failures => !res,
+        }
+    }
+}
+
+/// When the header fields get quoted
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Quoting {
+    /// Only when they hold the delimiter, a quote, a line
+    /// break or surrounding whitespace
+    Needed,
+    /// Always
+    Always,
+    /// Never, the fields are written as they are
+    Never,
+}
+
+/// Writes truth tables as Comma or Tab Separated Values
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CsvWriter<'h> {
+    delimiter: char,
+    values: ValueStyle,
+    rows: Rows,
+    quoting: Quoting,
+    result_header: &'h str,
+}
+
+impl<'h> CsvWriter<'h> {
+    /// A writer for Comma Separated Values
+    pub fn csv() -> Self {
+        Self {
+            delimiter: ',',
+            values: ValueStyle::Digits,
+            rows: Rows::All,
+            quoting: Quoting::Needed,
+            result_header: "Result",
+        }
+    }
+
+    /// A writer for Tab Separated Values
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            ..Self::csv()
+        }
+    }
+
+    /// The character between fields
+    pub fn delimiter(&self) -> char {
+        self.delimiter
+    }
+
+    /// Set the character between fields
+    pub fn set_delimiter(&mut self, delimiter: char) {
+        self.delimiter = delimiter;
+    }
+
+    /// How the values are written out
+    pub fn values(&self) -> ValueStyle {
+        self.values
+    }
+
+    /// Set how the values are written out
+    pub fn set_values(&mut self, values: ValueStyle) {
+        self.values = values;
+    }
+
+    /// Which states are written out
+    pub fn rows(&self) -> Rows {
+        self.rows
+    }
+
+    /// Set which states are written out
+    pub fn set_rows(&mut self, rows: Rows) {
+        self.rows = rows;
+    }
+
+    /// When the header fields get quoted
+    pub fn quoting(&self) -> Quoting {
+        self.quoting
+    }
+
+    /// Set when the header fields get quoted
+    pub fn set_quoting(&mut self, quoting: Quoting) {
+        self.quoting = quoting;
+    }
+
+    /// The header of the result column
+    pub fn result_header(&self) -> &'h str {
+        self.result_header
+    }
+
+    /// Set the header of the result column
+    pub fn set_result_header(&mut self, header: &'h str) {
+        self.result_header = header;
+    }
+
+    /// Write the header row, with the given variable names
+    /// followed by the result column
+    pub fn write_header<'n, W: Write>(
+        &self,
+        out: &mut W,
+        vars: impl IntoIterator<Item = &'n str>,
+    ) -> fmt::Result {
+        for var in vars {
+            self.write_field(out, var)?;
+            out.write_char(self.delimiter)?;
+        }
+        self.write_field(out, self.result_header)?;
+        out.write_char('\n')
+    }
+
+    /// Write a single row, with the given variable values
+    /// followed by the result
+    pub fn write_row<W: Write>(
+        &self,
+        out: &mut W,
+        vals: impl IntoIterator<Item = bool>,
+        res: bool,
+    ) -> fmt::Result {
+        for val in vals {
+            out.write_str(self.values.spell(val))?;
+            out.write_char(self.delimiter)?;
+        }
+        out.write_str(self.values.spell(res))?;
+        out.write_char('\n')
+    }
+
+    /// Write the whole table of the `tester`, naming its
+    /// variables with `vars`
+    pub fn write_named<'n, W: Write, E: Expression>(
+        &self,
+        out: &mut W,
+        tester: &Tester<E>,
+        vars: impl IntoIterator<Item = &'n str>,
+    ) -> fmt::Result {
+        self.write_header(out, vars)?;
+        for (state, res) in tester.eval().filter(|(_, res)| self.rows.includes(*res)) {
+            self.write_row(out, tester.var_vals(state), res)?;
+        }
+        Ok(())
+    }
+
+    /// Write the whole table of the `tester`, with the
+    /// variables named after their expression
+    #[cfg(feature = "parsing")]
+    pub fn write<W: Write>(&self, out: &mut W, tester: &Tester<Tokens>) -> fmt::Result {
+        self.write_named(out, tester, tester.vars().iter().copied())
+    }
+
+    fn write_field<W: Write>(&self, out: &mut W, field: &str) -> fmt::Result {
+        let quote = match self.quoting {
+            Quoting::Always => true,
+            Quoting::Never => false,
+            Quoting::Needed => {
+                field.contains(&[self.delimiter, '"', '\n', '\r'][..])
+                    || field.starts_with(char::is_whitespace)
+                    || field.ends_with(char::is_whitespace)
+            }
+        };
+
+        if quote {
+            out.write_char('"')?;
+            for (n, part) in field.split('"').enumerate() {
+                if n != 0 {
+                    out.write_str("\"\"")?;
+                }
+                out.write_str(part)?;
+            }
+            out.write_char('"')
+        } else {
+            out.write_str(field)
+        }
+    }
+}
+
+impl<'h> Default for CsvWriter<'h> {
+    fn default() -> Self {
+        Self::csv()
+    }
+}
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    fn named(writer: &CsvWriter, src: &str, vars: &[&str]) -> String {
+        let mut out = String::new();
+        writer
+            .write_named(&mut out, &Tester::parse(src), vars.iter().copied())
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn writes_csv() {
+        let mut out = String::new();
+        CsvWriter::csv()
+            .write(&mut out, &Tester::parse("a & !b"))
+            .unwrap();
+        assert_eq!(out, "a,b,Result\n0,0,0\n1,0,1\n0,1,0\n1,1,0\n");
+    }
+
+    #[test]
+    fn writes_tsv() {
+        let mut writer = CsvWriter::tsv();
+        writer.set_values(ValueStyle::Letters);
+        writer.set_rows(Rows::Failures);
+        writer.set_result_header("a | b");
+        assert_eq!(
+            named(&writer, "a | b", &["a", "b"]),
+            "a\tb\ta | b\nF\tF\tF\n"
+        );
+    }
+
+    #[test]
+    fn quotes_fields_when_needed() {
+        let writer = CsvWriter::csv();
+        assert_eq!(
+            named(&writer, "a & b", &["x,y", "say \"hi\""])
+                .lines()
+                .next(),
+            Some("\"x,y\",\"say \"\"hi\"\"\",Result")
+        );
+        assert_eq!(
+            named(&writer, "a ^ b", &[" a", "b\nc"]).lines().next(),
+            Some("\" a\",\"b")
+        );
+
+        // only the own delimiter needs quoting
+        assert_eq!(
+            named(&CsvWriter::tsv(), "a & b", &["x,y", "x\ty"])
+                .lines()
+                .next(),
+            Some("x,y\t\"x\ty\"\tResult")
+        );
+    }
+
+    #[test]
+    fn quotes_always_or_never() {
+        let mut writer = CsvWriter::csv();
+        writer.set_values(ValueStyle::Words);
+        writer.set_rows(Rows::Successes);
+        writer.set_quoting(Quoting::Always);
+        assert_eq!(
+            named(&writer, "a & b", &["a", "b"]),
+            "\"a\",\"b\",\"Result\"\ntrue,true,true\n"
+        );
+        writer.set_quoting(Quoting::Never);
+        assert_eq!(
+            named(&writer, "a & b", &["x,y", "\""]),
+            "x,y,\",Result\ntrue,true,true\n"
+        );
+    }
+}
@@ -0,0 +1,392 @@
+#[cfg(feature = "tester")]
+use crate::eval::{Expression, Tester};
+use crate::parsing::{Token, TokenLiterals, Tokens};
+#[cfg(feature = "tester")]
+use alloc::collections::btree_map::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+impl<'a> Tokens<'a> {
+    /// Write the syntax tree of the expression as a Graphviz
+    /// `digraph`, labelling the operators with the first of
+    /// their spellings in the default [`TokenLiterals`]
+    ///
+    /// [`TokenLiterals`]: `TokenLiterals`
+    pub fn write_dot<W: Write>(&self, out: &mut W) -> fmt::Result {
+        self.write_dot_with(out, &TokenLiterals::default())
+    }
+
+    /// Write the syntax tree of the expression as a Graphviz
+    /// `digraph`, labelling the operators with the first of
+    /// their spellings in the given [`TokenLiterals`]
+    ///
+    /// [`TokenLiterals`]: `TokenLiterals`
+    pub fn write_dot_with<W: Write>(&self, out: &mut W, literals: &TokenLiterals) -> fmt::Result {
+        out.write_str("digraph expr {\n")?;
+        let mut next = 0;
+        self.fold(|t, args: Vec<Result<usize, fmt::Error>>| {
+            let id = next;
+            next += 1;
+            let (label, shape) = match t {
+                Token::Var(name, _) => (name, "box"),
+                Token::Literal(true) => (literals.lit_true()[0].as_str(), "box"),
+                Token::Literal(false) => (literals.lit_false()[0].as_str(), "box"),
+                Token::Not => (literals.not()[0].as_str(), "circle"),
+                Token::And => (literals.and()[0].as_str(), "circle"),
+                Token::Xor => (literals.xor()[0].as_str(), "circle"),
+                Token::Or => (literals.or()[0].as_str(), "circle"),
+                Token::Implication => (literals.implication()[0].as_str(), "circle"),
+                Token::Equality => (literals.equality()[0].as_str(), "circle"),
+                Token::LParen | Token::RParen => unreachable!("Parenthesis in Final Experssion"),
+            };
+            write!(out, "  n{} [label=", id)?;
+            write_label(out, label)?;
+            writeln!(out, ", shape={}];", shape)?;
+            for child in args {
+                writeln!(out, "  n{} -> n{};", id, child?)?;
+            }
+            Ok(id)
+        })?;
+        out.write_str("}\n")
+    }
+}
+
+/// Write a quoted DOT string
+fn write_label<W: Write>(out: &mut W, label: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in label.chars() {
+        if c == '"' || c == '\\' {
+            out.write_char('\\')?;
+        }
+        out.write_char(c)?;
+    }
+    out.write_char('"')
+}
+
+/// A node of a [`DecisionDiagram`]
+///
+/// [`DecisionDiagram`]: `DecisionDiagram`
+#[cfg(feature = "tester")]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum DecisionNode {
+    /// The value of the function
+    Leaf(bool),
+    /// A test of the variable at index `var`, going on to the
+    /// node at index `low` when it is `false`, and to the node
+    /// at index `high` when it is `true`
+    Branch {
+        /// The index of the tested variable
+        var: usize,
+        /// The node for when the variable is `false`
+        low: usize,
+        /// The node for when the variable is `true`
+        high: usize,
+    },
+}
+
+/// A binary decision tree or diagram of the function
+/// of a [`Tester`], testing the variables in a given order
+///
+/// [`Tester`]: `Tester`
+#[cfg(feature = "tester")]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DecisionDiagram {
+    order: Vec<usize>,
+    nodes: Vec<DecisionNode>,
+    root: usize,
+}
+
+#[cfg(feature = "tester")]
+impl DecisionDiagram {
+    /// The full decision tree, testing every variable on
+    /// every path, in the given `order` of variable indices
+    pub fn tree<E: Expression>(tester: &Tester<E>, order: &[usize]) -> Self {
+        Self::build(tester, order, false)
+    }
+
+    /// The reduced ordered binary decision diagram, where
+    /// redundant tests are left out and equal sub diagrams
+    /// are shared, in the given `order` of variable indices
+    pub fn reduced<E: Expression>(tester: &Tester<E>, order: &[usize]) -> Self {
+        Self::build(tester, order, true)
+    }
+
+    fn build<E: Expression>(tester: &Tester<E>, order: &[usize], reduce: bool) -> Self {
+        let mut seen = 0usize;
+        for &v in order {
+            assert!(v < tester.var_count(), "Variable out of range");
+            assert!(seen & (1 << v) == 0, "Variable tested twice");
+            seen |= 1 << v;
+        }
+        assert_eq!(
+            order.len(),
+            tester.var_count(),
+            "Not every variable is tested"
+        );
+
+        let mut dd = Self {
+            order: order.to_vec(),
+            nodes: Vec::new(),
+            root: 0,
+        };
+        let mut unique = BTreeMap::new();
+        dd.root = dd.expand(tester, 0, 0, reduce, &mut unique);
+        dd
+    }
+
+    /// Shannon expand the function from the `depth`th
+    /// variable of the order on, with the variables
+    /// before it fixed as in `state`
+    fn expand<E: Expression>(
+        &mut self,
+        tester: &Tester<E>,
+        depth: usize,
+        state: usize,
+        reduce: bool,
+        unique: &mut BTreeMap<DecisionNode, usize>,
+    ) -> usize {
+        let node = match self.order.get(depth) {
+            None => DecisionNode::Leaf(tester.expr().eval(state)),
+            Some(&var) => {
+                let low = self.expand(tester, depth + 1, state, reduce, unique);
+                let high = self.expand(tester, depth + 1, state | 1 << var, reduce, unique);
+                if reduce && low == high {
+                    return low;
+                }
+                DecisionNode::Branch { var, low, high }
+            }
+        };
+
+        if reduce {
+            if let Some(&id) = unique.get(&node) {
+                return id;
+            }
+            unique.insert(node, self.nodes.len());
+        }
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// The order the variables are tested in
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Every node, each child before its parents
+    pub fn nodes(&self) -> &[DecisionNode] {
+        &self.nodes
+    }
+
+    /// The index of the node every path starts from
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Write the diagram as a Graphviz `digraph`, naming the
+    /// variables with `vars`. Edges for a `true` variable are
+    /// solid, those for a `false` one are dashed.
+    pub fn write_dot<W: Write>(&self, out: &mut W, vars: &[&str]) -> fmt::Result {
+        out.write_str("digraph decision {\n")?;
+        for (id, node) in self.nodes.iter().enumerate() {
+            match *node {
+                DecisionNode::Leaf(val) => {
+                    writeln!(out, "  n{} [label=\"{}\", shape=box];", id, val as u8)?
+                }
+                DecisionNode::Branch { var, low, high } => {
+                    write!(out, "  n{} [label=", id)?;
+                    write_label(out, vars[var])?;
+                    out.write_str(", shape=circle];\n")?;
+                    writeln!(out, "  n{} -> n{} [style=solid];", id, high)?;
+                    writeln!(out, "  n{} -> n{} [style=dashed];", id, low)?;
+                }
+            }
+        }
+        out.write_str("}\n")
+    }
+}
+
+#[cfg(feature = "tester")]
+impl<'t> Tester<Tokens<'t>> {
+    /// The reduced ordered binary decision diagram of the
+    /// expression, testing the variables in the order
+    /// they first appear in it
+    pub fn decision_diagram(&self) -> DecisionDiagram {
+        let order: Vec<usize> = (0..self.var_count()).collect();
+        DecisionDiagram::reduced(self, &order)
+    }
+}
+
+#[cfg(all(test, feature = "tester"))]
+mod tests {
+    use super::*;
+    use crate::parsing::Parser;
+    use alloc::string::String;
+
+    fn dot(tester: &Tester<Tokens>, dd: &DecisionDiagram) -> String {
+        let mut out = String::new();
+        dd.write_dot(&mut out, tester.vars()).unwrap();
+        out
+    }
+
+    /// The leaf reached by following the diagram for `state`
+    fn follow(dd: &DecisionDiagram, state: usize) -> bool {
+        let mut id = dd.root();
+        loop {
+            match dd.nodes()[id] {
+                DecisionNode::Leaf(val) => return val,
+                DecisionNode::Branch { var, low, high } => {
+                    id = if state & 1 << var == 0 { low } else { high };
+                }
+            }
+        }
+    }
+
+    /// Whether no two nodes are equal and no test is redundant
+    fn is_reduced(dd: &DecisionDiagram) -> bool {
+        let nodes = dd.nodes();
+        nodes.iter().enumerate().all(|(n, node)| {
+            !nodes[..n].contains(node)
+                && match *node {
+                    DecisionNode::Branch { low, high, .. } => low != high,
+                    DecisionNode::Leaf(_) => true,
+                }
+        })
+    }
+
+    #[test]
+    fn writes_syntax_trees() {
+        let mut out = String::new();
+        Parser::parse("!a & (b | true)")
+            .shunting_yard()
+            .write_dot(&mut out)
+            .unwrap();
+        assert_eq!(
+            out,
+            r#"digraph expr {
+  n0 [label="a", shape=box];
+  n1 [label="¬", shape=circle];
+  n1 -> n0;
+  n2 [label="b", shape=box];
+  n3 [label="true", shape=box];
+  n4 [label="∨", shape=circle];
+  n4 -> n2;
+  n4 -> n3;
+  n5 [label="∧", shape=circle];
+  n5 -> n1;
+  n5 -> n4;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn escapes_labels() {
+        let tokens = Tokens::new(
+            [Token::Var("say \"hi\\\"", 0)].to_vec(),
+            alloc::vec!["say \"hi\\\""],
+        );
+        let mut out = String::new();
+        tokens.write_dot(&mut out).unwrap();
+        assert_eq!(
+            out,
+            "digraph expr {\n  n0 [label=\"say \\\"hi\\\\\\\"\", shape=box];\n}\n"
+        );
+    }
+
+    #[test]
+    fn writes_decision_trees() {
+        let tester = Tester::parse("a & !b");
+        let tree = DecisionDiagram::tree(&tester, &[1, 0]);
+        assert_eq!(tree.order(), [1, 0]);
+        assert_eq!(
+            dot(&tester, &tree),
+            r#"digraph decision {
+  n0 [label="0", shape=box];
+  n1 [label="1", shape=box];
+  n2 [label="a", shape=circle];
+  n2 -> n1 [style=solid];
+  n2 -> n0 [style=dashed];
+  n3 [label="0", shape=box];
+  n4 [label="0", shape=box];
+  n5 [label="a", shape=circle];
+  n5 -> n4 [style=solid];
+  n5 -> n3 [style=dashed];
+  n6 [label="b", shape=circle];
+  n6 -> n5 [style=solid];
+  n6 -> n2 [style=dashed];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn writes_decision_diagrams() {
+        let tester = Tester::parse("a & c | b & c");
+        assert_eq!(
+            dot(&tester, &tester.decision_diagram()),
+            r#"digraph decision {
+  n0 [label="0", shape=box];
+  n1 [label="1", shape=box];
+  n2 [label="b", shape=circle];
+  n2 -> n1 [style=solid];
+  n2 -> n0 [style=dashed];
+  n3 [label="c", shape=circle];
+  n3 -> n2 [style=solid];
+  n3 -> n0 [style=dashed];
+  n4 [label="c", shape=circle];
+  n4 -> n1 [style=solid];
+  n4 -> n0 [style=dashed];
+  n5 [label="a", shape=circle];
+  n5 -> n4 [style=solid];
+  n5 -> n3 [style=dashed];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn reduced_shares_equal_diagrams() {
+        // the tree has 15 nodes, the diagram one for each
+        // parity of the variables tested so far
+        let tester = Tester::parse("a ^ b ^ c");
+        let tree = DecisionDiagram::tree(&tester, &[0, 1, 2]);
+        let dd = DecisionDiagram::reduced(&tester, &[0, 1, 2]);
+        assert_eq!(tree.nodes().len(), 15);
+        assert_eq!(dd.nodes().len(), 7);
+        assert!(!is_reduced(&tree));
+        assert!(is_reduced(&dd));
+        for state in 0..8 {
+            assert_eq!(follow(&tree, state), tester.expr().eval(state));
+            assert_eq!(follow(&dd, state), tester.expr().eval(state));
+        }
+    }
+
+    #[test]
+    fn reduced_drops_redundant_tests() {
+        let tester = Tester::parse("a & b | a & !b");
+        let dd = DecisionDiagram::reduced(&tester, &[1, 0]);
+        assert_eq!(
+            dd.nodes(),
+            [
+                DecisionNode::Leaf(false),
+                DecisionNode::Leaf(true),
+                DecisionNode::Branch {
+                    var: 0,
+                    low: 0,
+                    high: 1
+                },
+            ]
+        );
+        assert_eq!(dd.root(), 2);
+
+        let dd = Tester::parse("a | !a").decision_diagram();
+        assert_eq!(dd.nodes(), [DecisionNode::Leaf(true)]);
+        assert_eq!(dd.root(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Variable tested twice")]
+    fn rejects_repeated_variables() {
+        DecisionDiagram::reduced(&Tester::parse("a & b"), &[0, 0]);
+    }
+}
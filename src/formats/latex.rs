@@ -0,0 +1,378 @@
+#[cfg(feature = "parsing")]
+use crate::parsing::{Token, Tokens};
+#[cfg(feature = "tester")]
+use crate::{
+    eval::{Expression, Tester},
+    formats::{Rows, ValueStyle},
+};
+#[cfg(all(feature = "parsing", feature = "tester"))]
+use alloc::vec::Vec;
+#[cfg(feature = "parsing")]
+use alloc::{format, string::String};
+#[cfg(feature = "tester")]
+use core::fmt::{self, Write};
+
+#[cfg(feature = "parsing")]
+impl<'a> Tokens<'a> {
+    /// Write the expression out as LaTeX math, without
+    /// the surrounding `$`s, only adding the parenthesis
+    /// required by the precedence of the operators
+    pub fn to_latex(&self) -> String {
+        self.infix(
+            |t| match t {
+                Token::Literal(true) => "\\top",
+                Token::Literal(false) => "\\bot",
+                Token::Not => "\\neg ",
+                Token::And => "\\land",
+                Token::Xor => "\\oplus",
+                Token::Or => "\\lor",
+                Token::Implication => "\\rightarrow",
+                Token::Equality => "\\leftrightarrow",
+                _ => unreachable!("Only operators and literals are spelled"),
+            },
+            latex_var,
+        )
+    }
+}
+
+/// A variable name as LaTeX math, names longer than a
+/// single character are set as one italic word
+#[cfg(feature = "parsing")]
+fn latex_var(name: &str) -> String {
+    let mut escaped = String::new();
+    for c in name.chars() {
+        match c {
+            '_' | '#' | '$' | '%' | '&' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\\' => escaped.push_str("\\backslash "),
+            '^' => escaped.push_str("\\hat{}"),
+            '~' => escaped.push_str("\\sim "),
+            c => escaped.push(c),
+        }
+    }
+
+    if name.chars().count() == 1 {
+        escaped
+    } else {
+        format!("\\mathit{{{}}}", escaped)
+    }
+}
+
+/// How the rows where the expression is `true` stand out
+#[cfg(feature = "tester")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Highlight<'h> {
+    /// They do not
+    None,
+    /// Every cell is set in bold
+    Bold,
+    /// The row gets the given colour, which
+    /// requires the `colortbl` package
+    Color(&'h str),
+}
+
+/// Writes truth tables as a LaTeX `tabular` environment
+#[cfg(feature = "tester")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct LatexWriter<'h> {
+    booktabs: bool,
+    highlight: Highlight<'h>,
+    values: ValueStyle,
+    rows: Rows,
+    result_header: &'h str,
+    expression_header: bool,
+}
+
+#[cfg(feature = "tester")]
+impl<'h> LatexWriter<'h> {
+    /// A writer for plain `tabular`s, with no highlighting
+    pub fn new() -> Self {
+        Self {
+            booktabs: false,
+            highlight: Highlight::None,
+            values: ValueStyle::Digits,
+            rows: Rows::All,
+            result_header: "Result",
+            expression_header: false,
+        }
+    }
+
+    /// Whether the rules come from the `booktabs` package
+    pub fn booktabs(&self) -> bool {
+        self.booktabs
+    }
+
+    /// Set whether the rules come from the `booktabs` package
+    pub fn set_booktabs(&mut self, booktabs: bool) {
+        self.booktabs = booktabs;
+    }
+
+    /// How the rows where the expression is `true` stand out
+    pub fn highlight(&self) -> Highlight<'h> {
+        self.highlight
+    }
+
+    /// Set how the rows where the expression is `true` stand out
+    pub fn set_highlight(&mut self, highlight: Highlight<'h>) {
+        self.highlight = highlight;
+    }
+
+    /// How the values are written out
+    pub fn values(&self) -> ValueStyle {
+        self.values
+    }
+
+    /// Set how the values are written out
+    pub fn set_values(&mut self, values: ValueStyle) {
+        self.values = values;
+    }
+
+    /// Which states are written out
+    pub fn rows(&self) -> Rows {
+        self.rows
+    }
+
+    /// Set which states are written out
+    pub fn set_rows(&mut self, rows: Rows) {
+        self.rows = rows;
+    }
+
+    /// The header of the result column, as LaTeX text
+    pub fn result_header(&self) -> &'h str {
+        self.result_header
+    }
+
+    /// Set the header of the result column, as LaTeX text
+    pub fn set_result_header(&mut self, header: &'h str) {
+        self.result_header = header;
+    }
+
+    /// Whether the result column is headed by the
+    /// expression itself, instead of the result header,
+    /// when writing parsed expressions
+    pub fn expression_header(&self) -> bool {
+        self.expression_header
+    }
+
+    /// Set whether the result column is headed by the
+    /// expression itself, when writing parsed expressions
+    pub fn set_expression_header(&mut self, expression_header: bool) {
+        self.expression_header = expression_header;
+    }
+
+    /// Write the whole table of the `tester`, heading the
+    /// columns with the given LaTeX text
+    pub fn write_named<W: Write, E: Expression>(
+        &self,
+        out: &mut W,
+        tester: &Tester<E>,
+        vars: &[&str],
+        result: &str,
+    ) -> fmt::Result {
+        let (top, mid, bottom) = if self.booktabs {
+            ("\\toprule", "\\midrule", "\\bottomrule")
+        } else {
+            ("\\hline", "\\hline", "\\hline")
+        };
+
+        out.write_str("\\begin{tabular}{")?;
+        for n in 0..vars.len() {
+            if n != 0 && !self.booktabs {
+                out.write_char('|')?;
+            }
+            out.write_char('c')?;
+        }
+        out.write_str(if self.booktabs { "c}\n" } else { "||c}\n" })?;
+
+        writeln!(out, "  {}", top)?;
+        out.write_str("  ")?;
+        for var in vars {
+            write!(out, "{} & ", var)?;
+        }
+        writeln!(out, "{} \\\\", result)?;
+        writeln!(out, "  {}", mid)?;
+
+        for (state, res) in tester.eval().filter(|(_, res)| self.rows.includes(*res)) {
+            out.write_str("  ")?;
+            let bold = res && self.highlight == Highlight::Bold;
+            if let (true, Highlight::Color(color)) = (res, self.highlight) {
+                write!(out, "\\rowcolor{{{}}} ", color)?;
+            }
+            for (n, val) in tester.var_vals(state).chain(Some(res)).enumerate() {
+                if n != 0 {
+                    out.write_str(" & ")?;
+                }
+                if bold {
+                    write!(out, "\\textbf{{{}}}", self.values.spell(val))?;
+                } else {
+                    out.write_str(self.values.spell(val))?;
+                }
+            }
+            out.write_str(" \\\\\n")?;
+        }
+
+        writeln!(out, "  {}", bottom)?;
+        out.write_str("\\end{tabular}\n")
+    }
+
+    /// Write the whole table of the `tester`, with the
+    /// variables named after their expression and set
+    /// as math
+    #[cfg(feature = "parsing")]
+    pub fn write<W: Write>(&self, out: &mut W, tester: &Tester<Tokens>) -> fmt::Result {
+        let vars: Vec<String> = tester
+            .vars()
+            .iter()
+            .map(|v| format!("${}$", latex_var(v)))
+            .collect();
+        let vars: Vec<&str> = vars.iter().map(|v| v.as_str()).collect();
+
+        if self.expression_header {
+            let expr = format!("${}$", tester.expr().to_latex());
+            self.write_named(out, tester, &vars, &expr)
+        } else {
+            self.write_named(out, tester, &vars, self.result_header)
+        }
+    }
+}
+
+#[cfg(feature = "tester")]
+impl<'h> Default for LatexWriter<'h> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, feature = "parsing", feature = "tester"))]
+mod tests {
+    use super::*;
+    use crate::parsing::Parser;
+    use alloc::vec;
+
+    /// `(a_b -> !x&y) | c`, with names that need escaping
+    fn escaped() -> Tokens<'static> {
+        let toks = [
+            Token::Var("a_b", 0),
+            Token::Var("x&y", 1),
+            Token::Not,
+            Token::Implication,
+            Token::Var("c", 2),
+            Token::Or,
+        ];
+        Tokens::new(toks.to_vec(), vec!["a_b", "x&y", "c"])
+    }
+
+    fn table(writer: &LatexWriter, tester: &Tester<Tokens>) -> String {
+        let mut out = String::new();
+        writer.write(&mut out, tester).unwrap();
+        out
+    }
+
+    #[test]
+    fn writes_expressions() {
+        assert_eq!(
+            Parser::parse("!(a ^ b) & true <=> c | false")
+                .shunting_yard()
+                .to_latex(),
+            "\\neg (a \\oplus b) \\land \\top \\leftrightarrow c \\lor \\bot"
+        );
+        assert_eq!(
+            escaped().to_latex(),
+            "(\\mathit{a\\_b} \\rightarrow \\neg \\mathit{x\\&y}) \\lor c"
+        );
+    }
+
+    #[test]
+    fn escapes_variables() {
+        assert_eq!(latex_var("x"), "x");
+        assert_eq!(latex_var("_"), "\\_");
+        assert_eq!(latex_var("#$%{}"), "\\mathit{\\#\\$\\%\\{\\}}");
+        assert_eq!(
+            latex_var("a\\b^c~d"),
+            "\\mathit{a\\backslash b\\hat{}c\\sim d}"
+        );
+    }
+
+    #[test]
+    fn writes_plain_tables() {
+        assert_eq!(
+            table(&LatexWriter::new(), &Tester::parse("a & !b")),
+            "\\begin{tabular}{c|c||c}
+  \\hline
+  $a$ & $b$ & Result \\\\
+  \\hline
+  0 & 0 & 0 \\\\
+  1 & 0 & 1 \\\\
+  0 & 1 & 0 \\\\
+  1 & 1 & 0 \\\\
+  \\hline
+\\end{tabular}
+"
+        );
+    }
+
+    #[test]
+    fn writes_booktabs_in_bold() {
+        let mut writer = LatexWriter::new();
+        writer.set_booktabs(true);
+        writer.set_highlight(Highlight::Bold);
+        writer.set_expression_header(true);
+        assert_eq!(
+            table(&writer, &Tester::parse("a & !b")),
+            "\\begin{tabular}{ccc}
+  \\toprule
+  $a$ & $b$ & $a \\land \\neg b$ \\\\
+  \\midrule
+  0 & 0 & 0 \\\\
+  \\textbf{1} & \\textbf{0} & \\textbf{1} \\\\
+  0 & 1 & 0 \\\\
+  1 & 1 & 0 \\\\
+  \\bottomrule
+\\end{tabular}
+"
+        );
+    }
+
+    #[test]
+    fn colours_escaped_tables() {
+        let mut writer = LatexWriter::new();
+        writer.set_booktabs(true);
+        writer.set_highlight(Highlight::Color("green!20"));
+        writer.set_values(ValueStyle::Letters);
+        writer.set_rows(Rows::Successes);
+        writer.set_expression_header(true);
+        assert_eq!(
+            table(&writer, &Tester::with_tokens(escaped()))
+                .lines()
+                .nth(2),
+            Some(
+                "  $\\mathit{a\\_b}$ & $\\mathit{x\\&y}$ & $c$ & \
+                 $(\\mathit{a\\_b} \\rightarrow \\neg \\mathit{x\\&y}) \\lor c$ \\\\"
+            )
+        );
+
+        writer.set_rows(Rows::Failures);
+        writer.set_result_header("$r$");
+        writer.set_expression_header(false);
+        assert_eq!(
+            table(&writer, &Tester::with_tokens(escaped())),
+            "\\begin{tabular}{cccc}
+  \\toprule
+  $\\mathit{a\\_b}$ & $\\mathit{x\\&y}$ & $c$ & $r$ \\\\
+  \\midrule
+  T & T & F & F \\\\
+  \\bottomrule
+\\end{tabular}
+"
+        );
+        writer.set_rows(Rows::All);
+        assert_eq!(
+            table(&writer, &Tester::with_tokens(escaped()))
+                .lines()
+                .nth(4),
+            Some("  \\rowcolor{green!20} F & F & F & T \\\\")
+        );
+    }
+}
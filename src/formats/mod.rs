@@ -0,0 +1,43 @@
+//! Reading and writing truth tables and expressions in the
+//! formats of other tools, like CSV, LaTeX, PLA and SMT-LIB
+
+#[cfg(feature = "tester")]
+mod csv;
+#[cfg(feature = "tester")]
+pub use csv::{CsvWriter, Quoting, Rows, ValueStyle};
+
+#[cfg(feature = "tester")]
+mod text;
+#[cfg(feature = "tester")]
+pub use text::{TableStyle, TableWriter};
+
+mod latex;
+#[cfg(feature = "tester")]
+pub use latex::{Highlight, LatexWriter};
+
+#[cfg(feature = "parsing")]
+mod dot;
+#[cfg(all(feature = "parsing", feature = "tester"))]
+pub use dot::{DecisionDiagram, DecisionNode};
+
+#[cfg(feature = "parsing")]
+mod netlist;
+#[cfg(feature = "parsing")]
+pub use netlist::{Gate, Netlist};
+
+#[cfg(feature = "parsing")]
+mod smtlib;
+#[cfg(feature = "parsing")]
+pub use smtlib::{SmtError, SmtErrorKind};
+
+mod pla;
+#[cfg(all(feature = "parsing", feature = "tester"))]
+pub use pla::PlaOutput;
+pub use pla::PlaType;
+#[cfg(feature = "tester")]
+pub use pla::PlaWriter;
+#[cfg(feature = "parsing")]
+pub use pla::{Pla, PlaError, PlaErrorKind};
+
+#[cfg(all(feature = "parsing", feature = "tester"))]
+mod report;
@@ -0,0 +1,637 @@
+use crate::parsing::{Token, Tokens};
+use alloc::{
+    collections::btree_set::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+
+/// The reserved words of Verilog-2005, which
+/// can not be used as identifiers
+const VERILOG_KEYWORDS: &[&str] = &[
+    "always",
+    "and",
+    "assign",
+    "automatic",
+    "begin",
+    "buf",
+    "bufif0",
+    "bufif1",
+    "case",
+    "casex",
+    "casez",
+    "cell",
+    "cmos",
+    "config",
+    "deassign",
+    "default",
+    "defparam",
+    "design",
+    "disable",
+    "edge",
+    "else",
+    "end",
+    "endcase",
+    "endconfig",
+    "endfunction",
+    "endgenerate",
+    "endmodule",
+    "endprimitive",
+    "endspecify",
+    "endtable",
+    "endtask",
+    "event",
+    "for",
+    "force",
+    "forever",
+    "fork",
+    "function",
+    "generate",
+    "genvar",
+    "highz0",
+    "highz1",
+    "if",
+    "ifnone",
+    "incdir",
+    "include",
+    "initial",
+    "inout",
+    "input",
+    "instance",
+    "integer",
+    "join",
+    "large",
+    "liblist",
+    "library",
+    "localparam",
+    "macromodule",
+    "medium",
+    "module",
+    "nand",
+    "negedge",
+    "nmos",
+    "nor",
+    "noshowcancelled",
+    "not",
+    "notif0",
+    "notif1",
+    "or",
+    "output",
+    "parameter",
+    "pmos",
+    "posedge",
+    "primitive",
+    "pull0",
+    "pull1",
+    "pulldown",
+    "pullup",
+    "pulsestyle_onevent",
+    "pulsestyle_ondetect",
+    "rcmos",
+    "real",
+    "realtime",
+    "reg",
+    "release",
+    "repeat",
+    "rnmos",
+    "rpmos",
+    "rtran",
+    "rtranif0",
+    "rtranif1",
+    "scalared",
+    "showcancelled",
+    "signed",
+    "small",
+    "specify",
+    "specparam",
+    "strong0",
+    "strong1",
+    "supply0",
+    "supply1",
+    "table",
+    "task",
+    "time",
+    "tran",
+    "tranif0",
+    "tranif1",
+    "tri",
+    "tri0",
+    "tri1",
+    "triand",
+    "trior",
+    "trireg",
+    "unsigned",
+    "use",
+    "uwire",
+    "vectored",
+    "wait",
+    "wand",
+    "weak0",
+    "weak1",
+    "while",
+    "wire",
+    "wor",
+    "xnor",
+    "xor",
+];
+
+/// A primitive gate of a [`Netlist`]
+///
+/// [`Netlist`]: `Netlist`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Gate {
+    /// A constant value, with no inputs
+    Const(bool),
+    /// Copies its single input
+    Buf,
+    /// Negates its single input
+    Not,
+    /// The AND of its two inputs
+    And,
+    /// The OR of its two inputs
+    Or,
+    /// The XOR of its two inputs
+    Xor,
+    /// The XNOR of its two inputs
+    Xnor,
+}
+
+impl Gate {
+    /// The name of the Verilog primitive, `None`
+    /// for the constants which have none
+    pub fn verilog_name(&self) -> Option<&'static str> {
+        match self {
+            Gate::Const(_) => None,
+            Gate::Buf => Some("buf"),
+            Gate::Not => Some("not"),
+            Gate::And => Some("and"),
+            Gate::Or => Some("or"),
+            Gate::Xor => Some("xor"),
+            Gate::Xnor => Some("xnor"),
+        }
+    }
+
+    /// The rows of the BLIF single output cover, every
+    /// input combination for which the gate is `true`
+    fn blif_cover(&self) -> &'static [&'static str] {
+        match self {
+            Gate::Const(true) => &["1"],
+            Gate::Const(false) => &[],
+            Gate::Buf => &["1 1"],
+            Gate::Not => &["0 1"],
+            Gate::And => &["11 1"],
+            Gate::Or => &["1- 1", "-1 1"],
+            Gate::Xor => &["10 1", "01 1"],
+            Gate::Xnor => &["11 1", "00 1"],
+        }
+    }
+}
+
+/// A parsed expression as a combinational circuit of
+/// primitive gates, with a single output.
+///
+/// Every name in the circuit is a legal Verilog identifier,
+/// variables whose names are not are mangled by replacing
+/// every illegal character with `_`, prefixing a `_` when
+/// they start with a digit, and appending a `_` to keywords.
+/// The variables whose names are legal keep them, and mangled
+/// names that clash get a `_1`, `_2`, ... suffix, in the
+/// order the variables appear in the expression.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Netlist<'a> {
+    module: String,
+    output: String,
+    // the original names of the variables, in the
+    // same order as the `inputs`
+    vars: Vec<&'a str>,
+    inputs: Vec<String>,
+    gates: Vec<(Gate, String, Vec<String>)>,
+    assign: String,
+}
+
+impl<'a> Netlist<'a> {
+    /// The circuit of the `expr`, as a module `expr`
+    /// with a single output `out`
+    pub fn new(expr: &Tokens<'a>) -> Self {
+        Self::with_names(expr, "expr", "out")
+    }
+
+    /// The circuit of the `expr`, with the given module and
+    /// output names, which are mangled like the variables
+    pub fn with_names(expr: &Tokens<'a>, module: &str, output: &str) -> Self {
+        let mut taken: BTreeSet<String> = expr
+            .vars()
+            .iter()
+            .filter(|v| mangle(v) == **v)
+            .map(|v| v.to_string())
+            .collect();
+        let inputs: Vec<String> = expr
+            .vars()
+            .iter()
+            .map(|v| match mangle(v) {
+                legal if legal == *v => legal,
+                mangled => unique(&mut taken, mangled),
+            })
+            .collect();
+        let output = unique(&mut taken, mangle(output));
+
+        let mut gates: Vec<(Gate, String, Vec<String>)> = Vec::new();
+        let mut wire = |gate, ins: Vec<String>| {
+            let name = unique(&mut taken, format!("n{}", gates.len()));
+            gates.push((gate, name.clone(), ins));
+            name
+        };
+        // every operand is kept along with the
+        // Verilog expression that gives it
+        let (top, assign) = expr.fold(|t, mut args| match t {
+            Token::Literal(v) => {
+                let name = wire(Gate::Const(v), vec![]);
+                let lit = if v { "1'b1" } else { "1'b0" };
+                (name, (lit.to_string(), ATOM, ""))
+            }
+            Token::Var(_, idx) => (inputs[idx].clone(), (inputs[idx].clone(), ATOM, "")),
+            Token::Not => {
+                let (v, e) = args.pop().expect("Not has one operand");
+                (wire(Gate::Not, vec![v]), negate(e))
+            }
+            Token::Implication => {
+                let (r, re) = args.pop().expect("Binary operators have two operands");
+                let (l, le) = args.pop().expect("Binary operators have two operands");
+                let not_l = wire(Gate::Not, vec![l]);
+                let name = wire(Gate::Or, vec![not_l, r]);
+                (name, binary(negate(le), "|", 2, re))
+            }
+            t => {
+                let (r, re) = args.pop().expect("Binary operators have two operands");
+                let (l, le) = args.pop().expect("Binary operators have two operands");
+                let (gate, op, prec) = match t {
+                    Token::And => (Gate::And, "&", 4),
+                    Token::Xor => (Gate::Xor, "^", 3),
+                    Token::Or => (Gate::Or, "|", 2),
+                    Token::Equality => (Gate::Xnor, "~^", 3),
+                    _ => unreachable!("Parenthesis in Final Experssion"),
+                };
+                (wire(gate, vec![l, r]), binary(le, op, prec, re))
+            }
+        });
+
+        // the root gate drives the output itself, unless
+        // the output is only a copy of an input
+        match gates.last_mut() {
+            Some((_, name, _)) if *name == top => *name = output.clone(),
+            _ => gates.push((Gate::Buf, output.clone(), vec![top])),
+        }
+
+        Self {
+            module: mangle(module),
+            output,
+            vars: expr.vars().to_vec(),
+            inputs,
+            gates,
+            assign: assign.0,
+        }
+    }
+
+    /// The name of the module
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// The name of the output
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    /// The names of the inputs, one per variable
+    /// of the expression, in the same order
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// Every variable whose name had to be mangled,
+    /// along with the name of its input
+    pub fn renamed(&self) -> impl Iterator<Item = (&'a str, &str)> {
+        self.vars
+            .iter()
+            .zip(self.inputs.iter())
+            .filter(|(v, i)| **v != i.as_str())
+            .map(|(v, i)| (*v, i.as_str()))
+    }
+
+    /// The gates of the circuit, each with the wire it
+    /// drives and its inputs, in an order where every
+    /// wire is driven before it is used
+    pub fn gates(&self) -> impl Iterator<Item = (Gate, &str, &[String])> {
+        self.gates
+            .iter()
+            .map(|(g, out, ins)| (*g, out.as_str(), ins.as_slice()))
+    }
+
+    /// Write the circuit as a BLIF model, with
+    /// a `.names` block per gate
+    pub fn write_blif<W: Write>(&self, out: &mut W) -> fmt::Result {
+        for (var, input) in self.renamed() {
+            writeln!(out, "# {} -> {}", var, input)?;
+        }
+        writeln!(out, ".model {}", self.module)?;
+        out.write_str(".inputs")?;
+        for input in &self.inputs {
+            write!(out, " {}", input)?;
+        }
+        writeln!(out, "\n.outputs {}", self.output)?;
+
+        for (gate, wire, ins) in self.gates() {
+            out.write_str(".names")?;
+            for input in ins {
+                write!(out, " {}", input)?;
+            }
+            writeln!(out, " {}", wire)?;
+            for row in gate.blif_cover() {
+                writeln!(out, "{}", row)?;
+            }
+        }
+
+        out.write_str(".end\n")
+    }
+
+    /// Write the circuit as a Verilog module with
+    /// a single continuous `assign`
+    pub fn write_verilog<W: Write>(&self, out: &mut W) -> fmt::Result {
+        self.write_module(out, |out| {
+            writeln!(out, "  assign {} = {};", self.output, self.assign)
+        })
+    }
+
+    /// Write the circuit as a Verilog module made only of
+    /// instances of the primitive gates
+    pub fn write_verilog_gates<W: Write>(&self, out: &mut W) -> fmt::Result {
+        self.write_module(out, |out| {
+            let wires: Vec<&str> = self
+                .gates()
+                .map(|(_, wire, _)| wire)
+                .filter(|w| *w != self.output)
+                .collect();
+            if !wires.is_empty() {
+                writeln!(out, "  wire {};", wires.join(", "))?;
+            }
+
+            for (n, (gate, wire, ins)) in self.gates().enumerate() {
+                match gate.verilog_name() {
+                    Some(prim) => {
+                        writeln!(out, "  {} g{} ({}, {});", prim, n, wire, ins.join(", "))?
+                    }
+                    None => {
+                        let lit = if gate == Gate::Const(true) {
+                            "1'b1"
+                        } else {
+                            "1'b0"
+                        };
+                        writeln!(out, "  buf g{} ({}, {});", n, wire, lit)?
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn write_module<W: Write>(
+        &self,
+        out: &mut W,
+        body: impl FnOnce(&mut W) -> fmt::Result,
+    ) -> fmt::Result {
+        for (var, input) in self.renamed() {
+            writeln!(out, "// {} -> {}", var, input)?;
+        }
+        write!(out, "module {}(", self.module)?;
+        for input in &self.inputs {
+            write!(out, "{}, ", input)?;
+        }
+        writeln!(out, "{});", self.output)?;
+        for input in &self.inputs {
+            writeln!(out, "  input {};", input)?;
+        }
+        writeln!(out, "  output {};", self.output)?;
+        body(out)?;
+        out.write_str("endmodule\n")
+    }
+}
+
+/// The precedence of a Verilog expression that
+/// never needs parenthesis
+const ATOM: usize = 6;
+
+/// A Verilog expression, along with the precedence
+/// and spelling of its outermost operator
+type Verilog = (String, usize, &'static str);
+
+fn negate((e, prec, _): Verilog) -> Verilog {
+    if prec == ATOM {
+        (format!("~{}", e), ATOM, "")
+    } else {
+        (format!("~({})", e), ATOM, "")
+    }
+}
+
+/// Join two expressions with a Verilog operator, which associate
+/// to the left, unless both are the same associative operator
+fn binary(l: Verilog, op: &'static str, prec: usize, r: Verilog) -> Verilog {
+    let l = if l.1 < prec {
+        format!("({})", l.0)
+    } else {
+        l.0
+    };
+    let r = if r.1 < prec || (r.1 == prec && r.2 != op) {
+        format!("({})", r.0)
+    } else {
+        r.0
+    };
+    (format!("{} {} {}", l, op, r), prec, op)
+}
+
+/// Turn the name into a legal Verilog identifier
+fn mangle(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !out.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        out.insert(0, '_');
+    }
+    if VERILOG_KEYWORDS.contains(&out.as_str()) {
+        out.push('_');
+    }
+    out
+}
+
+/// Suffix the name until it is not `taken`, then take it
+pub(super) fn unique(taken: &mut BTreeSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut n = 1;
+    while taken.contains(&candidate) {
+        candidate = format!("{}_{}", name, n);
+        n += 1;
+    }
+    taken.insert(candidate.clone());
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::Parser;
+
+    fn blif(netlist: &Netlist) -> String {
+        let mut out = String::new();
+        netlist.write_blif(&mut out).unwrap();
+        out
+    }
+
+    fn verilog(netlist: &Netlist) -> String {
+        let mut out = String::new();
+        netlist.write_verilog(&mut out).unwrap();
+        out
+    }
+
+    fn verilog_gates(netlist: &Netlist) -> String {
+        let mut out = String::new();
+        netlist.write_verilog_gates(&mut out).unwrap();
+        out
+    }
+
+    /// `a & !b | c -> true`, with every kind of gate but XOR
+    fn implication() -> Netlist<'static> {
+        Netlist::new(&Parser::parse("a & !b | c -> true").shunting_yard())
+    }
+
+    /// Variables named after a keyword, and what
+    /// that keyword would be mangled to
+    fn mangled() -> Netlist<'static> {
+        let toks = [
+            Token::Var("wire", 0),
+            Token::Var("wire_", 1),
+            Token::Xor,
+            Token::Var("1x", 2),
+            Token::Var("a.b", 3),
+            Token::Equality,
+            Token::And,
+        ];
+        let tokens = Tokens::new(toks.to_vec(), vec!["wire", "wire_", "1x", "a.b"]);
+        Netlist::with_names(&tokens, "my mod", "n0")
+    }
+
+    #[test]
+    fn writes_blif() {
+        assert_eq!(
+            blif(&implication()),
+            ".model expr
+.inputs a b c
+.outputs out
+.names b n0
+0 1
+.names a n0 n1
+11 1
+.names n1 c n2
+1- 1
+-1 1
+.names n3
+1
+.names n2 n4
+0 1
+.names n4 n3 out
+1- 1
+-1 1
+.end
+"
+        );
+    }
+
+    #[test]
+    fn writes_verilog() {
+        assert_eq!(
+            verilog(&implication()),
+            "module expr(a, b, c, out);
+  input a;
+  input b;
+  input c;
+  output out;
+  assign out = ~(a & ~b | c) | 1'b1;
+endmodule
+"
+        );
+    }
+
+    #[test]
+    fn writes_verilog_gates() {
+        assert_eq!(
+            verilog_gates(&implication()),
+            "module expr(a, b, c, out);
+  input a;
+  input b;
+  input c;
+  output out;
+  wire n0, n1, n2, n3, n4;
+  not g0 (n0, b);
+  and g1 (n1, a, n0);
+  or g2 (n2, n1, c);
+  buf g3 (n3, 1'b1);
+  not g4 (n4, n2);
+  or g5 (out, n4, n3);
+endmodule
+"
+        );
+    }
+
+    #[test]
+    fn copies_a_lone_input() {
+        let netlist = Netlist::new(&Parser::parse("a").shunting_yard());
+        assert_eq!(
+            blif(&netlist),
+            ".model expr\n.inputs a\n.outputs out\n.names a out\n1 1\n.end\n"
+        );
+        assert!(verilog(&netlist).contains("  assign out = a;\n"));
+        assert!(verilog_gates(&netlist).contains("  output out;\n  buf g0 (out, a);\n"));
+    }
+
+    #[test]
+    fn mangles_names() {
+        let netlist = mangled();
+        assert_eq!(netlist.module(), "my_mod");
+        assert_eq!(netlist.inputs(), ["wire__1", "wire_", "_1x", "a_b"]);
+        assert_eq!(
+            netlist.renamed().collect::<Vec<_>>(),
+            [("wire", "wire__1"), ("1x", "_1x"), ("a.b", "a_b")]
+        );
+        // the output keeps its name, and the wire it clashes with is renamed
+        assert_eq!(netlist.output(), "n0");
+        assert_eq!(
+            verilog_gates(&netlist),
+            "// wire -> wire__1
+// 1x -> _1x
+// a.b -> a_b
+module my_mod(wire__1, wire_, _1x, a_b, n0);
+  input wire__1;
+  input wire_;
+  input _1x;
+  input a_b;
+  output n0;
+  wire n0_1, n1;
+  xor g0 (n0_1, wire__1, wire_);
+  xnor g1 (n1, _1x, a_b);
+  and g2 (n0, n0_1, n1);
+endmodule
+"
+        );
+        assert!(verilog(&netlist).contains("  assign n0 = (wire__1 ^ wire_) & (_1x ~^ a_b);\n"));
+        assert!(blif(&netlist).starts_with(
+            "# wire -> wire__1\n# 1x -> _1x\n# a.b -> a_b\n.model my_mod\n.inputs wire__1 wire_ _1x a_b\n"
+        ));
+    }
+}
@@ -0,0 +1,733 @@
+#[cfg(all(feature = "parsing", feature = "tester"))]
+use crate::eval::state::state_index;
+#[cfg(feature = "tester")]
+use crate::eval::{Expression, State, Tester};
+#[cfg(all(feature = "parsing", feature = "tester"))]
+use crate::parsing::Tokens;
+#[cfg(feature = "parsing")]
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+#[cfg(feature = "tester")]
+use core::fmt::Write;
+
+/// Which sets of the function the cubes of a PLA describe
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PlaType {
+    /// Only the on-set, everything else is `false`
+    F,
+    /// The on-set and the don't-care set,
+    /// everything else is `false`
+    Fd,
+    /// The on-set and the off-set, everything
+    /// else is a don't-care
+    Fr,
+    /// The on-set, the off-set and the don't-care set,
+    /// everything else is a don't-care
+    Fdr,
+}
+
+impl PlaType {
+    /// The name of the type, as used by `.type`
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlaType::F => "f",
+            PlaType::Fd => "fd",
+            PlaType::Fr => "fr",
+            PlaType::Fdr => "fdr",
+        }
+    }
+
+    #[cfg(feature = "parsing")]
+    fn has_dont_cares(&self) -> bool {
+        *self == PlaType::Fd || *self == PlaType::Fdr
+    }
+
+    #[cfg(feature = "parsing")]
+    fn has_off_set(&self) -> bool {
+        *self == PlaType::Fr || *self == PlaType::Fdr
+    }
+}
+
+impl fmt::Display for PlaType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Writes expressions as an espresso PLA, with
+/// one cube per state
+#[cfg(feature = "tester")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct PlaWriter<'h> {
+    off_set: bool,
+    result_header: &'h str,
+}
+
+#[cfg(feature = "tester")]
+impl<'h> PlaWriter<'h> {
+    /// A writer for the on-set of the expressions
+    pub fn new() -> Self {
+        Self {
+            off_set: false,
+            result_header: "f",
+        }
+    }
+
+    /// Whether the off-set is written out too, as `.type fr`
+    pub fn off_set(&self) -> bool {
+        self.off_set
+    }
+
+    /// Set whether the off-set is written out too, as `.type fr`
+    pub fn set_off_set(&mut self, off_set: bool) {
+        self.off_set = off_set;
+    }
+
+    /// The name of the output of a single expression
+    pub fn result_header(&self) -> &'h str {
+        self.result_header
+    }
+
+    /// Set the name of the output of a single expression
+    pub fn set_result_header(&mut self, header: &'h str) {
+        self.result_header = header;
+    }
+
+    /// Write the `outputs`, each a name along with its `Tester`,
+    /// as inputs named with `vars`.
+    ///
+    /// Every `Tester` must be over exactly `vars.len()` variables.
+    pub fn write_named<W: Write, E: Expression>(
+        &self,
+        out: &mut W,
+        vars: &[&str],
+        outputs: &[(&str, &Tester<E>)],
+    ) -> fmt::Result {
+        for (name, tester) in outputs {
+            assert_eq!(
+                tester.var_count(),
+                vars.len(),
+                "The output {} is not over the given inputs",
+                name
+            );
+        }
+
+        self.write_cubes(
+            out,
+            vars.iter().copied(),
+            outputs.iter().map(|(name, _)| *name),
+            vars.len(),
+            |state, n| outputs[n].1.expr().eval(state),
+            outputs.len(),
+        )
+    }
+
+    /// Write a single expression, with its variables as the inputs
+    #[cfg(feature = "parsing")]
+    pub fn write<W: Write>(&self, out: &mut W, tester: &Tester<Tokens>) -> fmt::Result {
+        self.write_named(out, tester.vars(), &[(self.result_header, tester)])
+    }
+
+    /// Write several expressions as the outputs of a single PLA,
+    /// each a name along with its `Tester`.
+    ///
+    /// The inputs are every variable of the expressions, in
+    /// the order they first show up.
+    #[cfg(feature = "parsing")]
+    pub fn write_multi<W: Write>(
+        &self,
+        out: &mut W,
+        outputs: &[(&str, &Tester<Tokens>)],
+    ) -> fmt::Result {
+        let mut vars: Vec<&str> = Vec::new();
+        for (_, tester) in outputs {
+            for var in tester.vars() {
+                if !vars.contains(var) {
+                    vars.push(var);
+                }
+            }
+        }
+        // where each variable of every output sits in the inputs
+        let places: Vec<Vec<usize>> = outputs
+            .iter()
+            .map(|(_, tester)| {
+                tester
+                    .vars()
+                    .iter()
+                    .map(|var| vars.iter().position(|v| v == var).unwrap())
+                    .collect()
+            })
+            .collect();
+
+        self.write_cubes(
+            out,
+            vars.iter().copied(),
+            outputs.iter().map(|(name, _)| *name),
+            vars.len(),
+            |state, n| {
+                let local = places[n]
+                    .iter()
+                    .enumerate()
+                    .fold(0, |idx, (v, &p)| idx | ((state >> p) & 1) << v);
+                outputs[n].1.expr().eval(local)
+            },
+            outputs.len(),
+        )
+    }
+
+    fn write_cubes<'n, W: Write>(
+        &self,
+        out: &mut W,
+        inputs: impl Iterator<Item = &'n str>,
+        outputs: impl Iterator<Item = &'n str>,
+        input_count: usize,
+        eval: impl Fn(usize, usize) -> bool,
+        output_count: usize,
+    ) -> fmt::Result {
+        let written = |state| self.off_set || (0..output_count).any(|n| eval(state, n));
+
+        writeln!(out, ".i {}", input_count)?;
+        writeln!(out, ".o {}", output_count)?;
+        out.write_str(".ilb")?;
+        for name in inputs {
+            write!(out, " {}", name)?;
+        }
+        out.write_str("\n.ob")?;
+        for name in outputs {
+            write!(out, " {}", name)?;
+        }
+        out.write_char('\n')?;
+        let type_ = if self.off_set {
+            PlaType::Fr
+        } else {
+            PlaType::F
+        };
+        writeln!(out, ".type {}", type_)?;
+        let count = (0..1 << input_count).filter(|&s| written(s)).count();
+        writeln!(out, ".p {}", count)?;
+
+        for state in (0..1 << input_count).filter(|&s| written(s)) {
+            for v in 0..input_count {
+                out.write_char(if state.var_at(v) { '1' } else { '0' })?;
+            }
+            out.write_char(' ')?;
+            for n in 0..output_count {
+                out.write_char(if eval(state, n) { '1' } else { '0' })?;
+            }
+            out.write_char('\n')?;
+        }
+
+        out.write_str(".e\n")
+    }
+}
+
+#[cfg(feature = "tester")]
+impl<'h> Default for PlaWriter<'h> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Why a PLA could not be read
+#[cfg(feature = "parsing")]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum PlaErrorKind {
+    /// A directive this reader does not support
+    UnknownDirective(String),
+    /// A directive is missing its number, or it is not one
+    InvalidNumber,
+    /// A `.type` other than `f`, `fd`, `fr` and `fdr`
+    InvalidType(String),
+    /// A cube showed up before both `.i` and `.o`
+    MissingSize,
+    /// More inputs than the states can be counted with
+    TooManyInputs,
+    /// `.ilb` or `.ob` names a different amount of
+    /// variables than `.i` or `.o` declared
+    NameCount {
+        /// The declared amount
+        expected: usize,
+        /// The amount of names
+        found: usize,
+    },
+    /// A cube with the wrong amount of values
+    CubeWidth {
+        /// The inputs and outputs declared
+        expected: usize,
+        /// The values on the line
+        found: usize,
+    },
+    /// A character that is not a value of a cube
+    InvalidValue(char),
+}
+
+/// An error found while reading a PLA, along with
+/// the line, counting from 1, that it was found on
+#[cfg(feature = "parsing")]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct PlaError {
+    line: usize,
+    kind: PlaErrorKind,
+}
+
+#[cfg(feature = "parsing")]
+impl PlaError {
+    /// The line the error was found on, counting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// What went wrong
+    pub fn kind(&self) -> &PlaErrorKind {
+        &self.kind
+    }
+}
+
+#[cfg(feature = "parsing")]
+impl fmt::Display for PlaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            PlaErrorKind::UnknownDirective(d) => write!(f, "unsupported directive `{}`", d),
+            PlaErrorKind::InvalidNumber => f.write_str("expected a number"),
+            PlaErrorKind::InvalidType(t) => write!(f, "unknown type `{}`", t),
+            PlaErrorKind::MissingSize => f.write_str("cube found before `.i` and `.o`"),
+            PlaErrorKind::TooManyInputs => f.write_str("too many inputs"),
+            PlaErrorKind::NameCount { expected, found } => {
+                write!(f, "expected {} names, found {}", expected, found)
+            }
+            PlaErrorKind::CubeWidth { expected, found } => {
+                write!(f, "expected {} values, found {}", expected, found)
+            }
+            PlaErrorKind::InvalidValue(c) => write!(f, "`{}` is not a cube value", c),
+        }
+    }
+}
+
+/// What a cube says about one of the outputs
+#[cfg(feature = "parsing")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Part {
+    On,
+    Off,
+    DontCare,
+    Nothing,
+}
+
+#[cfg(feature = "parsing")]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+struct Cube {
+    value: usize,
+    mask: usize,
+    outputs: Vec<Part>,
+}
+
+/// A multiple output function read from an espresso PLA
+#[cfg(feature = "parsing")]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Pla {
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    type_: PlaType,
+    cubes: Vec<Cube>,
+}
+
+#[cfg(feature = "parsing")]
+impl Pla {
+    /// Read a PLA, any input or output without a name
+    /// is named after its place, as `x0` or `f0`
+    pub fn parse(src: &str) -> Result<Self, PlaError> {
+        let mut inputs: Option<usize> = None;
+        let mut outputs: Option<usize> = None;
+        // the names are checked once the amounts are known,
+        // along with the line they were found on
+        let mut input_names: Option<(usize, Vec<String>)> = None;
+        let mut output_names: Option<(usize, Vec<String>)> = None;
+        let mut type_ = PlaType::Fd;
+        let mut cubes = Vec::new();
+
+        for (n, line) in src.lines().enumerate() {
+            let err = |kind| PlaError { line: n + 1, kind };
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with('.') {
+                let mut words = line.split_whitespace();
+                let directive = words.next().unwrap();
+                let number = |w: Option<&str>| {
+                    w.and_then(|w| w.parse::<usize>().ok())
+                        .ok_or_else(|| err(PlaErrorKind::InvalidNumber))
+                };
+                match directive {
+                    ".i" => {
+                        let i = number(words.next())?;
+                        if i >= usize::MAX.count_ones() as usize {
+                            return Err(err(PlaErrorKind::TooManyInputs));
+                        }
+                        inputs = Some(i);
+                    }
+                    ".o" => outputs = Some(number(words.next())?),
+                    // the amount of cubes is only a hint
+                    ".p" => {
+                        number(words.next())?;
+                    }
+                    ".ilb" => input_names = Some((n + 1, words.map(String::from).collect())),
+                    ".ob" => output_names = Some((n + 1, words.map(String::from).collect())),
+                    ".type" => {
+                        type_ = match words.next() {
+                            Some("f") => PlaType::F,
+                            Some("fd") => PlaType::Fd,
+                            Some("fr") => PlaType::Fr,
+                            Some("fdr") => PlaType::Fdr,
+                            t => {
+                                let t = t.unwrap_or("").into();
+                                return Err(err(PlaErrorKind::InvalidType(t)));
+                            }
+                        }
+                    }
+                    ".e" | ".end" => break,
+                    d => return Err(err(PlaErrorKind::UnknownDirective(d.into()))),
+                }
+                continue;
+            }
+
+            let (i, o) = match (inputs, outputs) {
+                (Some(i), Some(o)) => (i, o),
+                _ => return Err(err(PlaErrorKind::MissingSize)),
+            };
+            let vals: Vec<char> = line
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != '|')
+                .collect();
+            if vals.len() != i + o {
+                return Err(err(PlaErrorKind::CubeWidth {
+                    expected: i + o,
+                    found: vals.len(),
+                }));
+            }
+
+            let mut cube = Cube {
+                value: 0,
+                mask: 0,
+                outputs: Vec::with_capacity(o),
+            };
+            for (v, c) in vals[..i].iter().enumerate() {
+                match c {
+                    '0' => (),
+                    '1' => cube.value |= 1 << v,
+                    '-' | '2' => cube.mask |= 1 << v,
+                    c => return Err(err(PlaErrorKind::InvalidValue(*c))),
+                }
+            }
+            for c in &vals[i..] {
+                cube.outputs.push(match c {
+                    '1' | '4' => Part::On,
+                    '0' | '3' if type_.has_off_set() => Part::Off,
+                    '-' | '2' if type_.has_dont_cares() => Part::DontCare,
+                    '0' | '3' | '-' | '2' | '~' => Part::Nothing,
+                    c => return Err(err(PlaErrorKind::InvalidValue(*c))),
+                });
+            }
+            cubes.push(cube);
+        }
+
+        let inputs = inputs.unwrap_or(0);
+        let outputs = outputs.unwrap_or(0);
+        let names = |names: Option<(usize, Vec<String>)>, count, prefix| match names {
+            Some((line, names)) if names.len() != count => Err(PlaError {
+                line,
+                kind: PlaErrorKind::NameCount {
+                    expected: count,
+                    found: names.len(),
+                },
+            }),
+            Some((_, names)) => Ok(names),
+            None => Ok((0..count).map(|n| format!("{}{}", prefix, n)).collect()),
+        };
+
+        Ok(Self {
+            inputs: names(input_names, inputs, "x")?,
+            outputs: names(output_names, outputs, "f")?,
+            type_,
+            cubes,
+        })
+    }
+
+    /// The names of the inputs, the first being the
+    /// least significant bit of a state
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// The names of the outputs
+    pub fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    /// Which sets the cubes describe
+    pub fn pla_type(&self) -> PlaType {
+        self.type_
+    }
+
+    /// The amount of cubes
+    pub fn cube_count(&self) -> usize {
+        self.cubes.len()
+    }
+
+    /// The value of the `output` for the state `idx`, `None`
+    /// when it is a don't-care.
+    ///
+    /// A state that is both in the on-set and either the
+    /// off-set or the don't-care set is a don't-care.
+    ///
+    /// # Panics
+    ///
+    /// When there is no such output, or no such state
+    /// over the inputs
+    pub fn value(&self, output: usize, idx: usize) -> Option<bool> {
+        assert!(output < self.outputs.len(), "The PLA has no such output");
+        assert!(
+            idx < 1 << self.inputs.len(),
+            "The state is not over the inputs of the PLA"
+        );
+
+        let (mut on, mut off) = (false, false);
+        for cube in self.cubes.iter().filter(|c| idx & !c.mask == c.value) {
+            match cube.outputs[output] {
+                Part::On => on = true,
+                Part::Off => off = true,
+                Part::DontCare => return None,
+                Part::Nothing => (),
+            }
+        }
+
+        match (on, off) {
+            (true, true) => None,
+            (true, false) => Some(true),
+            (false, true) => Some(false),
+            (false, false) if self.type_.has_off_set() => None,
+            (false, false) => Some(false),
+        }
+    }
+
+    /// A single output of the PLA, as an [`Expression`]
+    ///
+    /// [`Expression`]: `Expression`
+    #[cfg(feature = "tester")]
+    pub fn output(&self, output: usize) -> Option<PlaOutput<'_>> {
+        if output < self.outputs.len() {
+            Some(PlaOutput { pla: self, output })
+        } else {
+            None
+        }
+    }
+
+    /// A [`Tester`] over a single output of the PLA
+    ///
+    /// [`Tester`]: `Tester`
+    #[cfg(feature = "tester")]
+    pub fn tester(&self, output: usize) -> Option<Tester<PlaOutput<'_>>> {
+        self.output(output)
+            .map(|o| Tester::new(o, self.inputs.len()))
+    }
+
+    /// Every state where the `output` has a value that differs
+    /// from the expression of the `tester`, don't-cares
+    /// always agree
+    ///
+    /// # Panics
+    ///
+    /// When there is no such output, or the
+    /// `tester` is not over the inputs
+    #[cfg(feature = "tester")]
+    pub fn mismatches<'p, E: Expression>(
+        &'p self,
+        output: usize,
+        tester: &'p Tester<E>,
+    ) -> impl Iterator<Item = usize> + 'p {
+        assert_eq!(
+            tester.var_count(),
+            self.inputs.len(),
+            "The tester is not over the inputs of the PLA"
+        );
+        assert!(output < self.outputs.len(), "The PLA has no such output");
+
+        tester
+            .iterations()
+            .filter(move |&idx| match self.value(output, idx) {
+                Some(val) => val != tester.expr().eval(idx),
+                None => false,
+            })
+    }
+}
+
+/// A single output of a [`Pla`], don't-cares are `false`
+///
+/// [`Pla`]: `Pla`
+#[cfg(all(feature = "parsing", feature = "tester"))]
+#[derive(Debug, Clone, Copy)]
+pub struct PlaOutput<'p> {
+    pla: &'p Pla,
+    output: usize,
+}
+
+#[cfg(all(feature = "parsing", feature = "tester"))]
+impl<'p> PlaOutput<'p> {
+    /// The PLA the output belongs to
+    pub fn pla(&self) -> &'p Pla {
+        self.pla
+    }
+
+    /// The index of the output
+    pub fn index(&self) -> usize {
+        self.output
+    }
+}
+
+#[cfg(all(feature = "parsing", feature = "tester"))]
+impl<'p> Expression for PlaOutput<'p> {
+    fn eval<S: State>(&self, state: S) -> bool {
+        let idx = state_index(&state, self.pla.inputs.len());
+        self.pla.value(self.output, idx).unwrap_or(false)
+    }
+}
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    #[cfg(feature = "tester")]
+    use super::PlaWriter;
+    use super::{Pla, PlaType};
+    #[cfg(feature = "tester")]
+    use crate::eval::{Expression, Tester};
+    #[cfg(feature = "tester")]
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    /// `f0` is `x0 & x1`, and `f1` is `x0` with a
+    /// don't-care for the state where only `x1` is set
+    const SRC: &str = ".i 2\n.o 2\n.type fd\n11 11\n10 01\n01 0-\n.e\n";
+
+    #[test]
+    fn reads_values() {
+        let pla = Pla::parse(SRC).unwrap();
+        let f0: Vec<_> = (0..4).map(|idx| pla.value(0, idx)).collect();
+        let f1: Vec<_> = (0..4).map(|idx| pla.value(1, idx)).collect();
+        assert_eq!(f0, [Some(false), Some(false), Some(false), Some(true)]);
+        assert_eq!(f1, [Some(false), Some(true), None, Some(true)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "no such output")]
+    fn rejects_missing_outputs() {
+        Pla::parse(SRC).unwrap().value(2, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "not over the inputs")]
+    fn rejects_missing_states() {
+        Pla::parse(SRC).unwrap().value(0, 4);
+    }
+
+    fn values(pla: &Pla, output: usize) -> Vec<Option<bool>> {
+        (0..1 << pla.inputs().len())
+            .map(|idx| pla.value(output, idx))
+            .collect()
+    }
+
+    #[test]
+    fn reads_on_sets() {
+        // without don't-cares, `-` outputs say nothing
+        let pla = Pla::parse(".i 2\n.o 1\n.type f\n-1 1\n00 -\n").unwrap();
+        assert_eq!(pla.pla_type(), PlaType::F);
+        assert_eq!(pla.inputs(), ["x0", "x1"]);
+        assert_eq!(pla.outputs(), ["f0"]);
+        assert_eq!(
+            values(&pla, 0),
+            [Some(false), Some(false), Some(true), Some(true)]
+        );
+    }
+
+    #[test]
+    fn reads_off_sets() {
+        // what no cube mentions is a don't-care
+        let pla = Pla::parse(".i 2\n.o 1\n.type fr\n11 1\n0- 0\n").unwrap();
+        assert_eq!(pla.pla_type(), PlaType::Fr);
+        assert_eq!(
+            values(&pla, 0),
+            [Some(false), None, Some(false), Some(true)]
+        );
+    }
+
+    #[test]
+    fn reads_all_sets() {
+        // state 3 is both on and off, so a don't-care
+        let src = ".i 2\n.o 1\n.ilb a b\n.ob y\n.type fdr\n10 1\n00 0\n01 -\n11 1\n1- 0\n";
+        let pla = Pla::parse(src).unwrap();
+        assert_eq!(pla.pla_type(), PlaType::Fdr);
+        assert_eq!(pla.inputs(), ["a", "b"]);
+        assert_eq!(pla.outputs(), ["y"]);
+        assert_eq!(pla.cube_count(), 5);
+        assert_eq!(values(&pla, 0), [Some(false), None, None, None]);
+    }
+
+    #[test]
+    #[cfg(feature = "tester")]
+    fn written_plas_read_back() {
+        let tester = Tester::parse("a & !b | c");
+        for &off_set in &[false, true] {
+            let mut writer = PlaWriter::new();
+            writer.set_off_set(off_set);
+            let mut out = String::new();
+            writer.write(&mut out, &tester).unwrap();
+
+            let pla = Pla::parse(&out).unwrap();
+            let type_ = if off_set { PlaType::Fr } else { PlaType::F };
+            assert_eq!(pla.pla_type(), type_);
+            assert_eq!(pla.inputs(), ["a", "b", "c"]);
+            assert_eq!(pla.outputs(), ["f"]);
+            assert_eq!(pla.cube_count(), if off_set { 8 } else { 5 });
+            for idx in tester.iterations() {
+                assert_eq!(pla.value(0, idx), Some(tester.expr().eval(idx)));
+            }
+            assert_eq!(pla.mismatches(0, &tester).count(), 0);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "tester")]
+    fn writes_several_outputs() {
+        let sum = Tester::parse("a ^ b");
+        let carry = Tester::parse("b & a");
+        let mut out = String::new();
+        PlaWriter::new()
+            .write_multi(&mut out, &[("s", &sum), ("c", &carry)])
+            .unwrap();
+        assert_eq!(
+            out,
+            ".i 2\n.o 2\n.ilb a b\n.ob s c\n.type f\n.p 3\n10 10\n01 10\n11 01\n.e\n"
+        );
+
+        let pla = Pla::parse(&out).unwrap();
+        assert_eq!(pla.mismatches(0, &sum).count(), 0);
+        assert_eq!(pla.mismatches(1, &sum).collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "tester")]
+    fn dont_cares_never_mismatch() {
+        let pla = Pla::parse(SRC).unwrap();
+        // `f1` is `false`, `true`, don't-care, `true`
+        let and = Tester::parse("a & b");
+        let or = Tester::parse("a | b");
+        assert_eq!(pla.mismatches(1, &and).collect::<Vec<_>>(), [1]);
+        assert_eq!(pla.mismatches(1, &or).count(), 0);
+        assert_eq!(pla.mismatches(0, &and).count(), 0);
+
+        // a don't-care is `false` as an expression
+        let f1 = pla.output(1).unwrap();
+        let on: Vec<_> = (0..4).filter(|&idx| f1.eval(idx)).collect();
+        assert_eq!(on, [1, 3]);
+    }
+}
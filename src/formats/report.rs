@@ -0,0 +1,135 @@
+use crate::eval::{Expectation, Failure, Report, Verdict};
+use alloc::format;
+use core::fmt::{self, Write};
+
+impl<'a> Report<'a> {
+    /// Write the report as a JSON object, with the amount of
+    /// records that `passed` and `failed`, and the `records`
+    /// themselves. A failed record has the assignment it fails
+    /// for as its `counterexample`, mapping every variable to
+    /// its value, or `null` when no assignment satisfies it.
+    pub fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        writeln!(out, "{{")?;
+        writeln!(out, "  \"passed\": {},", self.passed())?;
+        writeln!(out, "  \"failed\": {},", self.failed())?;
+        write!(out, "  \"records\": [")?;
+        for (n, v) in self.verdicts().iter().enumerate() {
+            if n != 0 {
+                write!(out, ",")?;
+            }
+            write!(out, "\n    ")?;
+            write_json_verdict(out, v)?;
+        }
+        if !self.verdicts().is_empty() {
+            write!(out, "\n  ")?;
+        }
+        writeln!(out, "]")?;
+        writeln!(out, "}}")
+    }
+
+    /// Write the report as a JUnit XML test suite called `suite`,
+    /// with a test case for every record. The message of every
+    /// failure says what the record was found to be.
+    pub fn write_junit<W: Write>(&self, out: &mut W, suite: &str) -> fmt::Result {
+        writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        write!(out, "<testsuite name=\"")?;
+        write_xml(out, suite)?;
+        writeln!(
+            out,
+            "\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+            self.verdicts().len(),
+            self.failed()
+        )?;
+
+        for v in self.verdicts() {
+            write!(out, "  <testcase name=\"")?;
+            write_xml(out, v.name())?;
+            write!(out, "\" classname=\"")?;
+            write_xml(out, suite)?;
+            if v.passed() {
+                writeln!(out, "\"/>")?;
+                continue;
+            }
+            write!(
+                out,
+                "\">\n    <failure type=\"{}\" message=\"",
+                v.expectation().name()
+            )?;
+            write_xml(out, &format!("{}", v))?;
+            writeln!(out, "\"/>\n  </testcase>")?;
+        }
+
+        writeln!(out, "</testsuite>")
+    }
+}
+
+fn write_json_verdict<W: Write>(out: &mut W, v: &Verdict) -> fmt::Result {
+    write!(out, "{{\"name\": ")?;
+    write_json_str(out, v.name())?;
+    write!(
+        out,
+        ", \"line\": {}, \"expectation\": \"{}\"",
+        v.line(),
+        v.expectation().name()
+    )?;
+    if let Expectation::Equivalent(other) = v.expectation() {
+        write!(out, ", \"other\": ")?;
+        write_json_str(out, other)?;
+    }
+    write!(out, ", \"passed\": {}", v.passed())?;
+
+    let vals = match v.failure() {
+        None => return write!(out, "}}"),
+        Some(Failure::Unsatisfiable) => return write!(out, ", \"counterexample\": null}}"),
+        Some(Failure::Assignment(vals)) => vals.as_slice(),
+        Some(Failure::Differs(cex)) => {
+            write!(
+                out,
+                ", \"left\": {}, \"right\": {}",
+                cex.left(),
+                cex.right()
+            )?;
+            cex.assignment()
+        }
+    };
+    write!(out, ", \"counterexample\": {{")?;
+    for (n, (name, val)) in vals.iter().enumerate() {
+        if n != 0 {
+            write!(out, ", ")?;
+        }
+        write_json_str(out, name)?;
+        write!(out, ": {}", val)?;
+    }
+    write!(out, "}}}}")
+}
+
+/// Write `s` as a JSON string, quotes included
+fn write_json_str<W: Write>(out: &mut W, s: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\t' => out.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+/// Write `s` escaped for use in XML text and attributes
+fn write_xml<W: Write>(out: &mut W, s: &str) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            '&' => out.write_str("&amp;")?,
+            '<' => out.write_str("&lt;")?,
+            '>' => out.write_str("&gt;")?,
+            '"' => out.write_str("&quot;")?,
+            '\'' => out.write_str("&apos;")?,
+            c => out.write_char(c)?,
+        }
+    }
+    Ok(())
+}
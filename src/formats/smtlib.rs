@@ -0,0 +1,758 @@
+use super::netlist::unique;
+use crate::parsing::{tree::Node, Token, Tokens};
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+
+/// Symbols that can not be used as the name of a
+/// variable without quoting them, as they are either
+/// reserved or a function of the Bool theory
+const SMT_RESERVED: &[&str] = &[
+    "!",
+    "_",
+    "as",
+    "exists",
+    "forall",
+    "let",
+    "match",
+    "par",
+    "true",
+    "false",
+    "not",
+    "and",
+    "or",
+    "xor",
+    "=>",
+    "=",
+    "ite",
+    "distinct",
+    "BINARY",
+    "DECIMAL",
+    "HEXADECIMAL",
+    "NUMERAL",
+    "STRING",
+];
+
+impl<'a> Tokens<'a> {
+    /// Write the expression as an SMT-LIB 2 term, with the
+    /// variables renamed as by [`smtlib_renamed`]
+    ///
+    /// [`smtlib_renamed`]: `Tokens::smtlib_renamed`
+    pub fn to_smtlib(&self) -> String {
+        let mut out = String::new();
+        write_term(&mut out, &Node::from_tokens(self), &symbols(self.vars()))
+            .expect("Strings can always be written to");
+        out
+    }
+
+    /// Write an SMT-LIB 2 script declaring every variable as
+    /// a `Bool` constant, and asserting the expression. The
+    /// variables renamed as by [`smtlib_renamed`] are listed
+    /// in comments first.
+    ///
+    /// [`smtlib_renamed`]: `Tokens::smtlib_renamed`
+    pub fn write_smtlib<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let symbols = symbols(self.vars());
+        for (var, symbol) in self.smtlib_renamed() {
+            writeln!(out, "; {} -> {}", var, symbol)?;
+        }
+        for symbol in &symbols {
+            out.write_str("(declare-const ")?;
+            write_symbol(out, symbol)?;
+            out.write_str(" Bool)\n")?;
+        }
+        out.write_str("(assert ")?;
+        write_term(out, &Node::from_tokens(self), &symbols)?;
+        out.write_str(")\n")
+    }
+
+    /// Every variable whose name can not be written as an
+    /// SMT-LIB 2 symbol, along with the symbol it is written as
+    ///
+    /// Not even a quoted symbol can hold `|` or `\`, so they are
+    /// replaced with `_`. Names that then clash with another
+    /// variable get a `_1`, `_2`, ... suffix.
+    pub fn smtlib_renamed(&self) -> Vec<(&'a str, String)> {
+        self.vars()
+            .iter()
+            .zip(symbols(self.vars()))
+            .filter(|(v, s)| **v != s.as_str())
+            .map(|(v, s)| (*v, s))
+            .collect()
+    }
+
+    /// Read an SMT-LIB 2 term over the core Bool theory, with
+    /// its variables indexed in the order they first appear
+    pub fn from_smtlib_term(src: &'a str) -> Result<Self, SmtError> {
+        let mut reader = Reader::new(src);
+        let term = reader
+            .sexp()?
+            .ok_or_else(|| reader.error(SmtErrorKind::UnexpectedEnd))?;
+        if let Some(extra) = reader.sexp()? {
+            return Err(SmtError::at(src, extra.text(), SmtErrorKind::Trailing));
+        }
+
+        let mut vars = Vars {
+            names: Vec::new(),
+            declared: false,
+        };
+        let node = vars.term(src, &term, &mut Vec::new())?;
+        Ok(node.to_tokens(vars.names))
+    }
+
+    /// Read an SMT-LIB 2 script as the AND of all of its
+    /// assertions, over the `Bool` constants it declares,
+    /// indexed in the order they are declared.
+    ///
+    /// Commands that do not change the assertions, such as
+    /// `set-logic` or `check-sat`, are skipped.
+    pub fn from_smtlib(src: &'a str) -> Result<Self, SmtError> {
+        let mut reader = Reader::new(src);
+        let mut vars = Vars {
+            names: Vec::new(),
+            declared: true,
+        };
+        let mut asserts = Vec::new();
+
+        while let Some(cmd) = reader.sexp()? {
+            let items = match &cmd {
+                Sexp::List(items, _) if !items.is_empty() => items,
+                _ => return Err(SmtError::at(src, cmd.text(), SmtErrorKind::ExpectedCommand)),
+            };
+            let name = match items[0] {
+                Sexp::Atom(name, false) => name,
+                ref s => return Err(SmtError::at(src, s.text(), SmtErrorKind::ExpectedCommand)),
+            };
+            let unsupported =
+                || SmtError::at(src, cmd.text(), SmtErrorKind::Unsupported(name.to_string()));
+
+            match name {
+                "declare-const" | "declare-fun" => {
+                    let (sym, sort) = match (name, &items[1..]) {
+                        ("declare-const", [sym, sort]) => (sym, sort),
+                        ("declare-fun", [sym, Sexp::List(args, _), sort]) if args.is_empty() => {
+                            (sym, sort)
+                        }
+                        _ => return Err(unsupported()),
+                    };
+                    match (sym, sort) {
+                        (Sexp::Str(text), _) => {
+                            return Err(SmtError::at(src, text, SmtErrorKind::StringLiteral))
+                        }
+                        (Sexp::Atom(sym, _), Sexp::Atom("Bool", false)) => {
+                            if vars.names.contains(sym) {
+                                return Err(SmtError::at(
+                                    src,
+                                    sym,
+                                    SmtErrorKind::Redeclared(sym.to_string()),
+                                ));
+                            }
+                            vars.names.push(sym);
+                        }
+                        (_, sort) => {
+                            return Err(SmtError::at(
+                                src,
+                                sort.text(),
+                                SmtErrorKind::Unsupported(sort.text().into()),
+                            ))
+                        }
+                    }
+                }
+                "assert" => match &items[1..] {
+                    [term] => asserts.push(vars.term(src, term, &mut Vec::new())?),
+                    _ => return Err(SmtError::at(src, cmd.text(), SmtErrorKind::Arity)),
+                },
+                "set-logic" | "set-info" | "set-option" | "check-sat" | "get-model"
+                | "get-value" | "get-info" | "get-option" | "echo" | "exit" => (),
+                _ => return Err(unsupported()),
+            }
+        }
+
+        Ok(Node::from_chain(Token::And, asserts).to_tokens(vars.names))
+    }
+}
+
+/// Why an SMT-LIB 2 script or term could not be read
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum SmtErrorKind {
+    /// The input ended inside an expression, or before one
+    UnexpectedEnd,
+    /// A `)` without a matching `(`
+    UnexpectedClose,
+    /// More than a single term was given
+    Trailing,
+    /// A top level expression that is not a command
+    ExpectedCommand,
+    /// A command, sort or function outside of the
+    /// supported subset of the Bool theory
+    Unsupported(String),
+    /// A function or command given the wrong amount of arguments
+    Arity,
+    /// A malformed `let`
+    InvalidLet,
+    /// A symbol that was never declared
+    Undeclared(String),
+    /// A constant that was declared twice
+    Redeclared(String),
+    /// A string literal where a symbol or term was expected
+    StringLiteral,
+}
+
+/// An error found while reading SMT-LIB 2, along
+/// with the byte offset it was found at
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SmtError {
+    offset: usize,
+    kind: SmtErrorKind,
+}
+
+impl SmtError {
+    /// The error `kind`, found at the start of `part`,
+    /// which must be a slice of `src`
+    fn at(src: &str, part: &str, kind: SmtErrorKind) -> Self {
+        Self {
+            offset: part.as_ptr() as usize - src.as_ptr() as usize,
+            kind,
+        }
+    }
+
+    /// The byte offset into the input the error was found at
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// What went wrong
+    pub fn kind(&self) -> &SmtErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for SmtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "offset {}: ", self.offset)?;
+        match &self.kind {
+            SmtErrorKind::UnexpectedEnd => f.write_str("unexpected end of input"),
+            SmtErrorKind::UnexpectedClose => f.write_str("unexpected `)`"),
+            SmtErrorKind::Trailing => f.write_str("expected a single term"),
+            SmtErrorKind::ExpectedCommand => f.write_str("expected a command"),
+            SmtErrorKind::Unsupported(s) => write!(f, "`{}` is not supported", s),
+            SmtErrorKind::Arity => f.write_str("wrong amount of arguments"),
+            SmtErrorKind::InvalidLet => f.write_str("malformed `let`"),
+            SmtErrorKind::Undeclared(s) => write!(f, "`{}` was never declared", s),
+            SmtErrorKind::Redeclared(s) => write!(f, "`{}` was declared twice", s),
+            SmtErrorKind::StringLiteral => f.write_str("expected a symbol, found a string"),
+        }
+    }
+}
+
+/// An S-expression, borrowing from the input
+#[derive(Debug)]
+enum Sexp<'a> {
+    /// An atom, and whether it was a quoted `|symbol|`,
+    /// in which case the bars are left out
+    Atom(&'a str, bool),
+    /// A `"string"` literal, along with its quotes
+    Str(&'a str),
+    /// A list, along with its whole text
+    List(Vec<Sexp<'a>>, &'a str),
+}
+
+impl<'a> Sexp<'a> {
+    /// The input the expression was read from
+    fn text(&self) -> &'a str {
+        match self {
+            Sexp::Atom(s, _) | Sexp::Str(s) | Sexp::List(_, s) => s,
+        }
+    }
+}
+
+struct Reader<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn error(&self, kind: SmtErrorKind) -> SmtError {
+        SmtError {
+            offset: self.pos,
+            kind,
+        }
+    }
+
+    /// Skip any whitespace and comments
+    fn skip(&mut self) {
+        loop {
+            let rest = &self.src[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with(';') {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Read the next expression, `None` at the end of the input
+    fn sexp(&mut self) -> Result<Option<Sexp<'a>>, SmtError> {
+        self.skip();
+        let start = self.pos;
+        let rest = &self.src[start..];
+
+        match rest.chars().next() {
+            None => Ok(None),
+            Some(')') => Err(self.error(SmtErrorKind::UnexpectedClose)),
+            Some('(') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip();
+                    match self.src[self.pos..].chars().next() {
+                        None => return Err(self.error(SmtErrorKind::UnexpectedEnd)),
+                        Some(')') => break,
+                        _ => items.push(self.sexp()?.expect("Only ends at the end of input")),
+                    }
+                }
+                self.pos += 1;
+                Ok(Some(Sexp::List(items, &self.src[start..self.pos])))
+            }
+            Some('|') => {
+                let len = rest[1..]
+                    .find('|')
+                    .ok_or_else(|| self.error(SmtErrorKind::UnexpectedEnd))?;
+                self.pos += len + 2;
+                Ok(Some(Sexp::Atom(&rest[1..len + 1], true)))
+            }
+            Some('"') => {
+                // a quote is escaped by doubling it
+                let mut end = 1;
+                loop {
+                    end += rest[end..]
+                        .find('"')
+                        .ok_or_else(|| self.error(SmtErrorKind::UnexpectedEnd))?
+                        + 1;
+                    if !rest[end..].starts_with('"') {
+                        break;
+                    }
+                    end += 1;
+                }
+                self.pos += end;
+                Ok(Some(Sexp::Str(&rest[..end])))
+            }
+            Some(_) => {
+                let len = rest
+                    .find(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == ';')
+                    .unwrap_or(rest.len());
+                self.pos += len;
+                Ok(Some(Sexp::Atom(&rest[..len], false)))
+            }
+        }
+    }
+}
+
+/// The variables found while reading, and whether
+/// they must be declared before they are used
+struct Vars<'a> {
+    names: Vec<&'a str>,
+    declared: bool,
+}
+
+impl<'a> Vars<'a> {
+    /// Turn a term into a tree, with the `let` bindings in
+    /// scope in `env`, innermost last
+    fn term(
+        &mut self,
+        src: &'a str,
+        term: &Sexp<'a>,
+        env: &mut Vec<(&'a str, Node<'a>)>,
+    ) -> Result<Node<'a>, SmtError> {
+        let items = match term {
+            Sexp::Atom(sym, quoted) => return self.symbol(src, sym, *quoted, env),
+            Sexp::Str(text) => return Err(SmtError::at(src, text, SmtErrorKind::StringLiteral)),
+            Sexp::List(items, _) if !items.is_empty() => items,
+            Sexp::List(_, text) => {
+                return Err(SmtError::at(
+                    src,
+                    text,
+                    SmtErrorKind::Unsupported("()".into()),
+                ))
+            }
+        };
+        let head = match items[0] {
+            Sexp::Atom(head, false) => head,
+            ref s => {
+                return Err(SmtError::at(
+                    src,
+                    s.text(),
+                    SmtErrorKind::Unsupported(s.text().into()),
+                ))
+            }
+        };
+        let arity = || SmtError::at(src, term.text(), SmtErrorKind::Arity);
+
+        if head == "let" {
+            return self.let_(src, term, items, env);
+        }
+        if head == "!" {
+            // annotations do not change the value of the term
+            return match items.get(1) {
+                Some(t) => self.term(src, t, env),
+                None => Err(arity()),
+            };
+        }
+
+        let mut args = Vec::with_capacity(items.len() - 1);
+        for arg in &items[1..] {
+            args.push(self.term(src, arg, env)?);
+        }
+
+        Ok(match (head, args.len()) {
+            ("not", 1) => Node::not(args.pop().unwrap()),
+            ("and", n) if n >= 2 => Node::from_chain(Token::And, args),
+            ("or", n) if n >= 2 => Node::from_chain(Token::Or, args),
+            ("xor", n) if n >= 2 => Node::from_chain(Token::Xor, args),
+            ("=>", n) if n >= 2 => Node::from_chain(Token::Implication, args),
+            ("=", n) if n >= 2 => Node::from_chain(
+                Token::And,
+                args.windows(2)
+                    .map(|w| Node::op(Token::Equality, w[0].clone(), w[1].clone()))
+                    .collect(),
+            ),
+            ("distinct", n) if n >= 2 => {
+                let mut pairs = Vec::new();
+                for i in 0..n {
+                    for j in (i + 1)..n {
+                        pairs.push(Node::not(Node::op(
+                            Token::Equality,
+                            args[i].clone(),
+                            args[j].clone(),
+                        )));
+                    }
+                }
+                Node::from_chain(Token::And, pairs)
+            }
+            ("ite", 3) => {
+                let e = args.pop().unwrap();
+                let t = args.pop().unwrap();
+                let c = args.pop().unwrap();
+                Node::op(
+                    Token::Or,
+                    Node::op(Token::And, c.clone(), t),
+                    Node::op(Token::And, Node::not(c), e),
+                )
+            }
+            ("not", _)
+            | ("and", _)
+            | ("or", _)
+            | ("xor", _)
+            | ("=>", _)
+            | ("=", _)
+            | ("distinct", _)
+            | ("ite", _) => return Err(arity()),
+            _ => {
+                return Err(SmtError::at(
+                    src,
+                    items[0].text(),
+                    SmtErrorKind::Unsupported(head.to_string()),
+                ))
+            }
+        })
+    }
+
+    /// Read a `(let ((x t) ...) body)`, where every
+    /// binding is read in the outer scope
+    fn let_(
+        &mut self,
+        src: &'a str,
+        term: &Sexp<'a>,
+        items: &[Sexp<'a>],
+        env: &mut Vec<(&'a str, Node<'a>)>,
+    ) -> Result<Node<'a>, SmtError> {
+        let invalid = || SmtError::at(src, term.text(), SmtErrorKind::InvalidLet);
+        let (bindings, body) = match items {
+            [_, Sexp::List(bindings, _), body] if !bindings.is_empty() => (bindings, body),
+            _ => return Err(invalid()),
+        };
+
+        let mut bound = Vec::with_capacity(bindings.len());
+        for binding in bindings {
+            match binding {
+                Sexp::List(pair, _) => match pair.as_slice() {
+                    [Sexp::Atom(name, _), value] => {
+                        bound.push((*name, self.term(src, value, env)?))
+                    }
+                    _ => return Err(invalid()),
+                },
+                _ => return Err(invalid()),
+            }
+        }
+
+        let depth = env.len();
+        env.extend(bound);
+        let body = self.term(src, body, env);
+        env.truncate(depth);
+        body
+    }
+
+    fn symbol(
+        &mut self,
+        src: &'a str,
+        sym: &'a str,
+        quoted: bool,
+        env: &[(&'a str, Node<'a>)],
+    ) -> Result<Node<'a>, SmtError> {
+        if let Some((_, node)) = env.iter().rev().find(|(name, _)| *name == sym) {
+            return Ok(node.clone());
+        }
+        match (sym, quoted) {
+            ("true", false) => return Ok(Node::Lit(true)),
+            ("false", false) => return Ok(Node::Lit(false)),
+            _ => (),
+        }
+
+        match self.names.iter().position(|n| *n == sym) {
+            Some(idx) => Ok(Node::Var(sym, idx)),
+            None if self.declared => Err(SmtError::at(
+                src,
+                sym,
+                SmtErrorKind::Undeclared(sym.to_string()),
+            )),
+            None => {
+                self.names.push(sym);
+                Ok(Node::Var(sym, self.names.len() - 1))
+            }
+        }
+    }
+}
+
+/// The symbol of every variable, see [`Tokens::smtlib_renamed`]
+///
+/// [`Tokens::smtlib_renamed`]: `Tokens::smtlib_renamed`
+fn symbols(vars: &[&str]) -> Vec<String> {
+    let lossless = |v: &str| !v.contains(['|', '\\']);
+    // the names that can be written keep them
+    let mut taken: BTreeSet<String> = vars
+        .iter()
+        .filter(|v| lossless(v))
+        .map(|v| v.to_string())
+        .collect();
+    vars.iter()
+        .map(|v| {
+            if lossless(v) {
+                v.to_string()
+            } else {
+                unique(&mut taken, v.replace(['|', '\\'], "_"))
+            }
+        })
+        .collect()
+}
+
+/// Write a tree as an SMT-LIB 2 term, with chains of the same
+/// associative operator written as one, and every variable
+/// written as its symbol
+fn write_term<W: Write>(out: &mut W, node: &Node, symbols: &[String]) -> fmt::Result {
+    match node {
+        Node::Lit(v) => write!(out, "{}", v),
+        Node::Var(_, idx) => write_symbol(out, &symbols[*idx]),
+        Node::Not(v) => {
+            out.write_str("(not ")?;
+            write_term(out, v, symbols)?;
+            out.write_char(')')
+        }
+        Node::Op(t, l, r) => {
+            let (name, terms) = match t {
+                Token::And => ("and", node.chain(*t)),
+                Token::Xor => ("xor", node.chain(*t)),
+                Token::Or => ("or", node.chain(*t)),
+                Token::Implication => ("=>", [&**l, &**r].to_vec()),
+                Token::Equality => ("=", [&**l, &**r].to_vec()),
+                _ => unreachable!("Parenthesis in Final Experssion"),
+            };
+            write!(out, "({}", name)?;
+            for term in terms {
+                out.write_char(' ')?;
+                write_term(out, term, symbols)?;
+            }
+            out.write_char(')')
+        }
+    }
+}
+
+/// Write a symbol, quoting it with `|`s when it is not
+/// a simple symbol, or it is reserved by the Bool theory.
+/// It must not hold `|` or `\`, see [`symbols`].
+///
+/// [`symbols`]: `symbols`
+fn write_symbol<W: Write>(out: &mut W, name: &str) -> fmt::Result {
+    let simple = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "~!@$%^&*_-+=<>.?/".contains(c))
+        && !SMT_RESERVED.contains(&name);
+
+    if simple {
+        out.write_str(name)
+    } else {
+        write!(out, "|{}|", name)
+    }
+}
+
+#[cfg(all(test, feature = "tester"))]
+mod tests {
+    use super::*;
+    use crate::{eval::Tester, parsing::Parser};
+
+    /// Check that the term reads as the infix `expr`
+    fn assert_reads(term: &str, expr: &str) {
+        let read = Tokens::from_smtlib_term(term).expect(term);
+        let expr = Parser::parse(expr).shunting_yard();
+        assert_eq!(read.vars(), expr.vars(), "{}", term);
+        assert_eq!(
+            Tester::with_tokens(read).truth_table(),
+            Tester::with_tokens(expr).truth_table(),
+            "{}",
+            term
+        );
+    }
+
+    /// Check that `tokens` are written as `term`, and
+    /// read back from both the term and a script
+    fn assert_round_trips(tokens: &Tokens, term: &str) {
+        assert_eq!(tokens.to_smtlib(), term);
+        let table = Tester::with_tokens(tokens.clone()).truth_table();
+
+        let read = Tokens::from_smtlib_term(term).expect(term);
+        assert_eq!(read.to_smtlib(), term);
+        assert_eq!(Tester::with_tokens(read).truth_table(), table, "{}", term);
+
+        let mut script = String::new();
+        tokens.write_smtlib(&mut script).unwrap();
+        let read = Tokens::from_smtlib(&script).expect(&script);
+        assert_eq!(read.vars().len(), tokens.vars().len(), "{}", script);
+        assert_eq!(Tester::with_tokens(read).truth_table(), table, "{}", script);
+    }
+
+    #[test]
+    fn round_trips_expressions() {
+        let cases = [
+            ("a & b & c | !d", "(or (and a b c) (not d))"),
+            ("(a -> b) = (c ^ d ^ a)", "(= (=> a b) (xor c d a))"),
+            ("!(a | false) <=> true", "(= (not (or a false)) true)"),
+            ("a", "a"),
+        ];
+        for (expr, term) in cases.iter() {
+            assert_round_trips(&Parser::parse(expr).shunting_yard(), term);
+        }
+    }
+
+    #[test]
+    fn reads_the_bool_theory() {
+        assert_reads("(let ((x (and a b)) (y c)) (or x (not y)))", "a & b | !c");
+        // bindings are read in the outer scope, and shadow it
+        assert_reads("(let ((a b)) (let ((b a)) (and a b)))", "b & b");
+        assert_reads("(ite a b c)", "a & b | !a & c");
+        assert_reads("(distinct a b c)", "!(a = b) & !(a = c) & !(b = c)");
+        assert_reads("(= a b c)", "(a = b) & (b = c)");
+        assert_reads("(=> a b c)", "a -> b -> c");
+        assert_reads("(! (xor a b) :named x)", "a ^ b");
+        assert_reads("; a comment\n(and |a| a)", "a & a");
+    }
+
+    #[test]
+    fn quotes_symbols() {
+        let a = [
+            Token::Var("not", 0),
+            Token::Var("1x", 1),
+            Token::And,
+            Token::Var("a b", 2),
+            Token::Or,
+        ];
+        let tokens = Tokens::new(a.to_vec(), alloc::vec!["not", "1x", "a b"]);
+        assert_round_trips(&tokens, "(or (and |not| |1x|) |a b|)");
+        assert!(tokens.smtlib_renamed().is_empty());
+        assert_eq!(
+            Tokens::from_smtlib_term("(or (and |not| |1x|) |a b|)")
+                .unwrap()
+                .vars(),
+            tokens.vars()
+        );
+    }
+
+    #[test]
+    fn renames_what_can_not_be_quoted() {
+        let a = [
+            Token::Var("a|b", 0),
+            Token::Var("a_b", 1),
+            Token::Xor,
+            Token::Var("c\\", 2),
+            Token::And,
+        ];
+        let tokens = Tokens::new(a.to_vec(), alloc::vec!["a|b", "a_b", "c\\"]);
+        assert_eq!(
+            tokens.smtlib_renamed(),
+            [("a|b", "a_b_1".to_string()), ("c\\", "c_".to_string())]
+        );
+        assert_round_trips(&tokens, "(and (xor a_b_1 a_b) c_)");
+
+        let mut script = String::new();
+        tokens.write_smtlib(&mut script).unwrap();
+        assert_eq!(
+            script,
+            "; a|b -> a_b_1\n\
+             ; c\\ -> c_\n\
+             (declare-const a_b_1 Bool)\n\
+             (declare-const a_b Bool)\n\
+             (declare-const c_ Bool)\n\
+             (assert (and (xor a_b_1 a_b) c_))\n"
+        );
+    }
+
+    #[test]
+    fn rejects_strings() {
+        let err = Tokens::from_smtlib_term("(and a \"b\")").unwrap_err();
+        assert_eq!(
+            (err.offset(), err.kind()),
+            (7, &SmtErrorKind::StringLiteral)
+        );
+        let err = Tokens::from_smtlib("(declare-const \"a\" Bool)").unwrap_err();
+        assert_eq!(
+            (err.offset(), err.kind()),
+            (15, &SmtErrorKind::StringLiteral)
+        );
+
+        // but they may be given to commands
+        let script = "(set-info :source \"a \"\"quoted\"\" (source)\")\n\
+                      (declare-const a Bool)\n\
+                      (assert a)";
+        assert_eq!(Tokens::from_smtlib(script).unwrap().vars(), ["a"]);
+    }
+
+    #[test]
+    fn reports_errors() {
+        let err = |src| {
+            let err = Tokens::from_smtlib(src).unwrap_err();
+            (err.offset(), err.kind().clone())
+        };
+        assert_eq!(err("(assert b)"), (8, SmtErrorKind::Undeclared("b".into())));
+        assert_eq!(
+            err("(declare-const a Int)"),
+            (17, SmtErrorKind::Unsupported("Int".into()))
+        );
+        assert_eq!(
+            err("(declare-const a Bool)(declare-const a Bool)"),
+            (37, SmtErrorKind::Redeclared("a".into()))
+        );
+        assert_eq!(err("(assert (not true false))"), (8, SmtErrorKind::Arity));
+        assert_eq!(err("(assert (let (a) a))"), (8, SmtErrorKind::InvalidLet));
+        assert_eq!(err("(assert true"), (12, SmtErrorKind::UnexpectedEnd));
+        assert_eq!(err(")"), (0, SmtErrorKind::UnexpectedClose));
+    }
+}
@@ -0,0 +1,366 @@
+#[cfg(feature = "parsing")]
+use crate::parsing::Tokens;
+use crate::{
+    eval::{Expression, Tester},
+    formats::{Rows, ValueStyle},
+};
+#[cfg(feature = "parsing")]
+use alloc::string::ToString;
+use core::fmt::{self, Write};
+
+/// The look of a text table
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TableStyle {
+    /// Lines drawn with the Unicode box drawing characters
+    Box,
+    /// Lines drawn with `+`, `-` and `|`
+    Ascii,
+    /// A GitHub flavoured Markdown table
+    Markdown,
+}
+
+/// Writes truth tables as aligned text
+///
+/// The columns are aligned by how wide their fields are
+/// displayed, where East Asian wide characters and emoji
+/// take up two columns and combining marks none. Sequences
+/// joined into a single emoji are not recognised, and are
+/// counted as wide as all of their parts.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct TableWriter<'h> {
+    style: TableStyle,
+    values: ValueStyle,
+    rows: Rows,
+    result_header: &'h str,
+    expression_header: bool,
+}
+
+impl<'h> TableWriter<'h> {
+    /// A writer for tables of the given style
+    pub fn new(style: TableStyle) -> Self {
+        Self {
+            style,
+            values: ValueStyle::Digits,
+            rows: Rows::All,
+            result_header: "Result",
+            expression_header: false,
+        }
+    }
+
+    /// The look of the table
+    pub fn style(&self) -> TableStyle {
+        self.style
+    }
+
+    /// Set the look of the table
+    pub fn set_style(&mut self, style: TableStyle) {
+        self.style = style;
+    }
+
+    /// How the values are written out
+    pub fn values(&self) -> ValueStyle {
+        self.values
+    }
+
+    /// Set how the values are written out
+    pub fn set_values(&mut self, values: ValueStyle) {
+        self.values = values;
+    }
+
+    /// Which states are written out
+    pub fn rows(&self) -> Rows {
+        self.rows
+    }
+
+    /// Set which states are written out
+    pub fn set_rows(&mut self, rows: Rows) {
+        self.rows = rows;
+    }
+
+    /// The header of the result column
+    pub fn result_header(&self) -> &'h str {
+        self.result_header
+    }
+
+    /// Set the header of the result column
+    pub fn set_result_header(&mut self, header: &'h str) {
+        self.result_header = header;
+    }
+
+    /// Whether the result column is headed by the
+    /// expression itself, instead of the result header,
+    /// when writing parsed expressions
+    pub fn expression_header(&self) -> bool {
+        self.expression_header
+    }
+
+    /// Set whether the result column is headed by the
+    /// expression itself, when writing parsed expressions
+    pub fn set_expression_header(&mut self, expression_header: bool) {
+        self.expression_header = expression_header;
+    }
+
+    /// Write the whole table of the `tester`, naming its
+    /// variables with `vars` and heading the result
+    /// column with `result`
+    pub fn write_named<W: Write, E: Expression>(
+        &self,
+        out: &mut W,
+        tester: &Tester<E>,
+        vars: &[&str],
+        result: &str,
+    ) -> fmt::Result {
+        let value_width = self
+            .width(self.values.spell(false))
+            .max(self.width(self.values.spell(true)));
+        let width = |header: &str| self.width(header).max(value_width);
+        let widths = || {
+            vars.iter()
+                .map(move |v| width(v))
+                .chain(core::iter::once(width(result)))
+        };
+
+        // header
+        match self.style {
+            TableStyle::Box => self.rule(out, widths(), '┌', '┬', '┐', '─')?,
+            TableStyle::Ascii => self.rule(out, widths(), '+', '+', '+', '-')?,
+            TableStyle::Markdown => (),
+        }
+        self.cells(out, widths().zip(vars.iter().copied().chain(Some(result))))?;
+        match self.style {
+            TableStyle::Box => self.rule(out, widths(), '├', '┼', '┤', '─')?,
+            TableStyle::Ascii => self.rule(out, widths(), '+', '+', '+', '-')?,
+            TableStyle::Markdown => {
+                out.write_char('|')?;
+                for w in widths() {
+                    out.write_char(':')?;
+                    for _ in 0..w {
+                        out.write_char('-')?;
+                    }
+                    out.write_str(":|")?;
+                }
+                out.write_char('\n')?;
+            }
+        }
+
+        // body
+        for (state, res) in tester.eval().filter(|(_, res)| self.rows.includes(*res)) {
+            let vals = tester
+                .var_vals(state)
+                .chain(Some(res))
+                .map(|v| self.values.spell(v));
+            self.cells(out, widths().zip(vals))?;
+        }
+
+        // footer
+        match self.style {
+            TableStyle::Box => self.rule(out, widths(), '└', '┴', '┘', '─'),
+            TableStyle::Ascii => self.rule(out, widths(), '+', '+', '+', '-'),
+            TableStyle::Markdown => Ok(()),
+        }
+    }
+
+    /// Write the whole table of the `tester`, with the
+    /// variables named after their expression
+    #[cfg(feature = "parsing")]
+    pub fn write<W: Write>(&self, out: &mut W, tester: &Tester<Tokens>) -> fmt::Result {
+        if self.expression_header {
+            let expr = tester.expr().to_string();
+            self.write_named(out, tester, tester.vars(), &expr)
+        } else {
+            self.write_named(out, tester, tester.vars(), self.result_header)
+        }
+    }
+
+    /// The amount of columns the field takes up once written
+    fn width(&self, field: &str) -> usize {
+        let escapes = match self.style {
+            TableStyle::Markdown => field.matches('|').count(),
+            _ => 0,
+        };
+        field.chars().map(char_width).sum::<usize>() + escapes
+    }
+
+    fn rule<W: Write>(
+        &self,
+        out: &mut W,
+        widths: impl Iterator<Item = usize>,
+        left: char,
+        mid: char,
+        right: char,
+        fill: char,
+    ) -> fmt::Result {
+        out.write_char(left)?;
+        for (n, w) in widths.enumerate() {
+            if n != 0 {
+                out.write_char(mid)?;
+            }
+            for _ in 0..w + 2 {
+                out.write_char(fill)?;
+            }
+        }
+        out.write_char(right)?;
+        out.write_char('\n')
+    }
+
+    fn cells<'f, W: Write>(
+        &self,
+        out: &mut W,
+        cells: impl Iterator<Item = (usize, &'f str)>,
+    ) -> fmt::Result {
+        let sep = match self.style {
+            TableStyle::Box => '│',
+            _ => '|',
+        };
+
+        out.write_char(sep)?;
+        for (w, field) in cells {
+            // center the field, leaning left
+            let pad = w - self.width(field);
+            let left = pad / 2;
+            out.write_char(' ')?;
+            for _ in 0..left {
+                out.write_char(' ')?;
+            }
+            match self.style {
+                TableStyle::Markdown => {
+                    for (n, part) in field.split('|').enumerate() {
+                        if n != 0 {
+                            out.write_str("\\|")?;
+                        }
+                        out.write_str(part)?;
+                    }
+                }
+                _ => out.write_str(field)?,
+            }
+            for _ in 0..pad - left {
+                out.write_char(' ')?;
+            }
+            out.write_char(' ')?;
+            out.write_char(sep)?;
+        }
+        out.write_char('\n')
+    }
+}
+
+impl<'h> Default for TableWriter<'h> {
+    fn default() -> Self {
+        Self::new(TableStyle::Box)
+    }
+}
+
+/// The amount of columns a character is displayed as, after
+/// Markus Kuhn's `wcwidth`, with the emoji blocks as wide
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        // combining marks, zero width spaces and joiners,
+        // and variation selectors
+        0x0300..=0x036F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F => 0,
+        // Hangul Jamo, CJK, Hangul syllables, fullwidth
+        // forms, emoji and the supplementary ideographs
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x2FFFD
+        | 0x30000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    fn table(writer: &TableWriter, src: &str) -> String {
+        let mut out = String::new();
+        writer.write(&mut out, &Tester::parse(src)).unwrap();
+        out
+    }
+
+    #[test]
+    fn writes_box_tables() {
+        assert_eq!(
+            table(&TableWriter::new(TableStyle::Box), "a & !b"),
+            "┌───┬───┬────────┐
+│ a │ b │ Result │
+├───┼───┼────────┤
+│ 0 │ 0 │   0    │
+│ 1 │ 0 │   1    │
+│ 0 │ 1 │   0    │
+│ 1 │ 1 │   0    │
+└───┴───┴────────┘
+"
+        );
+    }
+
+    #[test]
+    fn writes_ascii_tables() {
+        let mut writer = TableWriter::new(TableStyle::Ascii);
+        writer.set_values(ValueStyle::Words);
+        writer.set_rows(Rows::Successes);
+        assert_eq!(
+            table(&writer, "a | b"),
+            "+-------+-------+--------+
+|   a   |   b   | Result |
++-------+-------+--------+
+| true  | false |  true  |
+| false | true  |  true  |
+| true  | true  |  true  |
++-------+-------+--------+
+"
+        );
+    }
+
+    #[test]
+    fn writes_markdown_tables() {
+        let mut writer = TableWriter::new(TableStyle::Markdown);
+        writer.set_expression_header(true);
+        assert_eq!(
+            table(&writer, "a | b"),
+            "| a | b | a \\| b |
+|:-:|:-:|:------:|
+| 0 | 0 |   0    |
+| 1 | 0 |   1    |
+| 0 | 1 |   1    |
+| 1 | 1 |   1    |
+"
+        );
+    }
+
+    #[test]
+    fn aligns_wide_and_combining_characters() {
+        let mut out = String::new();
+        TableWriter::new(TableStyle::Box)
+            .write_named(&mut out, &Tester::parse("a & b"), &["猫", "e\u{301}"], "🎉")
+            .unwrap();
+        assert_eq!(
+            out,
+            "┌────┬───┬────┐
+│ 猫 │ e\u{301} │ 🎉 │
+├────┼───┼────┤
+│ 0  │ 0 │ 0  │
+│ 1  │ 0 │ 0  │
+│ 0  │ 1 │ 0  │
+│ 1  │ 1 │ 1  │
+└────┴───┴────┘
+"
+        );
+    }
+}
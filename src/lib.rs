@@ -3,7 +3,7 @@
 // This is for conditional compilation of code examples
 #![feature(external_doc)]
 
-#[cfg(feature = "parsing")]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 #[cfg(feature = "tester")]
@@ -11,3 +11,9 @@ pub mod eval;
 
 #[cfg(feature = "parsing")]
 pub mod parsing;
+
+#[cfg(any(feature = "tester", feature = "parsing"))]
+pub mod formats;
+
+#[cfg(feature = "macros")]
+pub use truth_tester_macros::bool_expr;
@@ -0,0 +1,412 @@
+//! The `truth-tester` command line tool
+
+mod repl;
+
+use std::{env, fs, process};
+use truth_tester::{
+    eval::{Batch, Tester},
+    formats::{CsvWriter, LatexWriter, Rows, TableStyle, TableWriter, ValueStyle},
+    parsing::{ParseError, TokenLiterals, Tokens},
+};
+
+const USAGE: &str = "\
+Usage: truth-tester [OPTIONS] <COMMAND>
+
+Commands:
+  table <expr>     Print the truth table of the expression
+  check <expr>     Exit with 0 only if the expression is a tautology
+  sat <expr>       Print an assignment that makes the expression true
+  equiv <a> <b>    Print an assignment where the expressions differ
+  verify <file>    Check every record of a batch file, where each line
+                   is `<name> <expectation> <expr>` and the expectation
+                   is tautology, contradiction, satisfiable or
+                   `equivalent <name>`
+  repl             Explore expressions interactively, see `:help`
+
+Options:
+  --rows <all|successes|failures>           Which rows `table` prints
+  --format <box|ascii|markdown|csv|tsv|latex>
+                                            How `table` prints them
+  --values <digits|words|letters>           How values are written
+  --report <text|json|junit>                How `verify` reports
+  --literals <FILE>                         Read the operator spellings
+                                            from a JSON file
+  --profile <NAME|FILE>                     Use a built-in profile, or read
+                                            one, merging it with those
+                                            given before it
+  --true, --false, --not, --and, --xor, --or, --implication,
+  --equality, --left-paren, --right-paren <SPELLING>
+                                            Spell the operator this way,
+                                            repeat to give several
+  -h, --help                                Print this message
+
+The built-in profiles are math-unicode, c-like, python, verilog
+and spreadsheet.
+
+Exits with 1 when the answer is negative, and 2 on an error.";
+
+/// How `table` prints the truth table
+#[derive(Clone, Copy)]
+enum Format {
+    Text(TableStyle),
+    Csv,
+    Tsv,
+    Latex,
+}
+
+/// How `verify` reports the verdicts
+#[derive(Clone, Copy)]
+enum ReportStyle {
+    Text,
+    Json,
+    Junit,
+}
+
+struct Options {
+    literals: TokenLiterals,
+    format: Format,
+    report: ReportStyle,
+    rows: Rows,
+    values: ValueStyle,
+    args: Vec<String>,
+}
+
+fn main() {
+    let code = match run(env::args().skip(1).collect()) {
+        Ok(code) => code,
+        Err(msg) => {
+            eprintln!("error: {}\n\nSee `truth-tester --help` for the usage.", msg);
+            2
+        }
+    };
+    process::exit(code);
+}
+
+fn run(args: Vec<String>) -> Result<i32, String> {
+    let opts = parse_args(args)?;
+    let parse = |expr| {
+        Tester::<Tokens>::try_parse_with_literals(expr, opts.literals.clone())
+            .map_err(|e| format!("{}\n  {}\n{}", e, expr, caret(expr, 2, &e)))
+    };
+
+    match opts.args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => {
+            println!("{}", USAGE);
+            Ok(0)
+        }
+        ["table", expr] => {
+            print!("{}", table(&opts, &parse(expr)?));
+            Ok(0)
+        }
+        ["check", expr] => {
+            let tester = parse(expr)?;
+            let failure = tester.failures().next();
+            match failure {
+                None => {
+                    println!("tautology");
+                    Ok(0)
+                }
+                Some(state) => {
+                    let vals = tester.var_vals(state);
+                    println!("fails for {}", assignment(&opts, tester.vars(), vals));
+                    Ok(1)
+                }
+            }
+        }
+        ["sat", expr] => {
+            let tester = parse(expr)?;
+            let success = tester.successes().next();
+            match success {
+                Some(state) => {
+                    let vals = tester.var_vals(state);
+                    println!("{}", assignment(&opts, tester.vars(), vals));
+                    Ok(0)
+                }
+                None => {
+                    println!("unsatisfiable");
+                    Ok(1)
+                }
+            }
+        }
+        ["equiv", a, b] => match parse(a)?.counterexample(&parse(b)?) {
+            None => {
+                println!("equivalent");
+                Ok(0)
+            }
+            Some(cex) => {
+                let (names, vals): (Vec<&str>, Vec<bool>) =
+                    cex.assignment().iter().copied().unzip();
+                println!(
+                    "{} gives {} and {}",
+                    assignment(&opts, &names, vals.into_iter()),
+                    opts.values.spell(cex.left()),
+                    opts.values.spell(cex.right())
+                );
+                Ok(1)
+            }
+        },
+        ["verify", file] => verify(&opts, file),
+        ["repl"] => repl::run(&opts),
+        [cmd, ..] => Err(match cmd {
+            "table" | "check" | "sat" | "verify" => format!("`{}` takes a single expression", cmd),
+            "repl" => "`repl` takes no arguments".into(),
+            "equiv" => "`equiv` takes two expressions".into(),
+            _ => format!("unknown command `{}`", cmd),
+        }),
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+    let mut opts = Options {
+        literals: TokenLiterals::default(),
+        format: Format::Text(TableStyle::Box),
+        report: ReportStyle::Text,
+        rows: Rows::All,
+        values: ValueStyle::Digits,
+        args: Vec::new(),
+    };
+    // spellings given as flags, which replace those of the
+    // literals once every flag has been read
+    let mut spellings: Vec<(String, Vec<String>)> = Vec::new();
+    // whether the defaults were replaced, so that
+    // further profiles get merged instead
+    let mut loaded = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "-h" || arg == "--help" {
+            opts.args.clear();
+            break;
+        }
+        if arg == "--" {
+            // expressions may start with a dash, so
+            // `--` ends the flags
+            opts.args.extend(args.by_ref());
+            break;
+        }
+        if !arg.starts_with("--") {
+            opts.args.push(arg);
+            continue;
+        }
+
+        let flag = &arg[2..];
+        let value = args
+            .next()
+            .ok_or_else(|| format!("`{}` needs a value", arg))?;
+        match flag {
+            "rows" => {
+                opts.rows = match value.as_str() {
+                    "all" => Rows::All,
+                    "successes" => Rows::Successes,
+                    "failures" => Rows::Failures,
+                    v => return Err(format!("unknown rows `{}`", v)),
+                }
+            }
+            "format" => {
+                opts.format = match value.as_str() {
+                    "box" => Format::Text(TableStyle::Box),
+                    "ascii" => Format::Text(TableStyle::Ascii),
+                    "markdown" => Format::Text(TableStyle::Markdown),
+                    "csv" => Format::Csv,
+                    "tsv" => Format::Tsv,
+                    "latex" => Format::Latex,
+                    v => return Err(format!("unknown format `{}`", v)),
+                }
+            }
+            "report" => {
+                opts.report = match value.as_str() {
+                    "text" => ReportStyle::Text,
+                    "json" => ReportStyle::Json,
+                    "junit" => ReportStyle::Junit,
+                    v => return Err(format!("unknown report `{}`", v)),
+                }
+            }
+            "values" => {
+                opts.values = match value.as_str() {
+                    "digits" => ValueStyle::Digits,
+                    "words" => ValueStyle::Words,
+                    "letters" => ValueStyle::Letters,
+                    v => return Err(format!("unknown values `{}`", v)),
+                }
+            }
+            "literals" => {
+                let file = fs::read_to_string(&value)
+                    .map_err(|e| format!("could not read `{}`: {}", value, e))?;
+                opts.literals = serde_json::from_str(&file)
+                    .map_err(|e| format!("could not read `{}`: {}", value, e))?;
+                loaded = true;
+            }
+            "profile" => {
+                let profile = match TokenLiterals::profile(&value) {
+                    Some(profile) => profile,
+                    None => {
+                        let file = fs::read_to_string(&value)
+                            .map_err(|e| format!("could not read `{}`: {}", value, e))?;
+                        TokenLiterals::from_profile(&file)
+                            .map_err(|e| format!("could not read `{}`: {}", value, e))?
+                    }
+                };
+                if loaded {
+                    opts.literals.merge(&profile);
+                } else {
+                    opts.literals = profile;
+                }
+                loaded = true;
+            }
+            _ if TOKENS.contains(&flag) => match spellings.iter_mut().find(|(f, _)| f == flag) {
+                Some((_, values)) => values.push(value),
+                None => spellings.push((flag.into(), vec![value])),
+            },
+            _ => return Err(format!("unknown option `{}`", arg)),
+        }
+    }
+
+    for (flag, values) in spellings {
+        if !set_spellings(&mut opts.literals, &flag, values) {
+            return Err(unknown_token(&flag));
+        }
+    }
+    opts.literals
+        .validate()
+        .map_err(|e| format!("the spellings can not be used, {}", e))?;
+
+    Ok(opts)
+}
+
+/// The names of the tokens, as the flags and the REPL spell them
+const TOKENS: [&str; 10] = [
+    "true",
+    "false",
+    "not",
+    "and",
+    "xor",
+    "or",
+    "implication",
+    "equality",
+    "left-paren",
+    "right-paren",
+];
+
+/// Check the records of a batch file, exiting with 1 if any fails
+fn verify(opts: &Options, file: &str) -> Result<i32, String> {
+    let src = fs::read_to_string(file).map_err(|e| format!("could not read `{}`: {}", file, e))?;
+    let batch = Batch::parse_with_literals(&src, opts.literals.clone())
+        .map_err(|e| format!("could not read `{}`: {}", file, e))?;
+    #[cfg(feature = "parallel")]
+    let report = batch.verify_par();
+    #[cfg(not(feature = "parallel"))]
+    let report = batch.verify();
+
+    let mut out = String::new();
+    let res = match opts.report {
+        ReportStyle::Text => {
+            for v in report.verdicts() {
+                let status = if v.passed() { "ok" } else { "FAILED" };
+                out.push_str(&format!("{}:{}: {}, {}\n", file, v.line(), status, v));
+            }
+            out.push_str(&format!(
+                "{} passed, {} failed\n",
+                report.passed(),
+                report.failed()
+            ));
+            Ok(())
+        }
+        ReportStyle::Json => report.write_json(&mut out),
+        ReportStyle::Junit => report.write_junit(&mut out, file),
+    };
+    res.expect("Strings can always be written to");
+    print!("{}", out);
+
+    Ok(if report.is_success() { 0 } else { 1 })
+}
+
+/// The spellings of the token called `token`
+fn spellings<'l>(lit: &'l TokenLiterals, token: &str) -> Option<&'l [String]> {
+    Some(match token {
+        "true" => lit.lit_true(),
+        "false" => lit.lit_false(),
+        "not" => lit.not(),
+        "and" => lit.and(),
+        "xor" => lit.xor(),
+        "or" => lit.or(),
+        "implication" => lit.implication(),
+        "equality" => lit.equality(),
+        "left-paren" => lit.left_paren(),
+        "right-paren" => lit.right_paren(),
+        _ => return None,
+    })
+}
+
+/// Replace the spellings of the token called `token`,
+/// returning whether there is such a token
+fn set_spellings(lit: &mut TokenLiterals, token: &str, values: Vec<String>) -> bool {
+    match token {
+        "true" => lit.set_lit_true(values),
+        "false" => lit.set_lit_false(values),
+        "not" => lit.set_not(values),
+        "and" => lit.set_and(values),
+        "xor" => lit.set_xor(values),
+        "or" => lit.set_or(values),
+        "implication" => lit.set_implication(values),
+        "equality" => lit.set_equality(values),
+        "left-paren" => lit.set_left_paren(values),
+        "right-paren" => lit.set_right_paren(values),
+        _ => return false,
+    }
+    true
+}
+
+/// The error for a token name that is not in [`TOKENS`]
+fn unknown_token(token: &str) -> String {
+    format!(
+        "unknown token `{}`, it is one of {}",
+        token,
+        TOKENS.join(", ")
+    )
+}
+
+/// A caret under the place in `line` the error was
+/// found at, where `line` starts `indent` columns in
+fn caret(line: &str, indent: usize, err: &ParseError) -> String {
+    let column = indent + line[..err.offset()].chars().count();
+    format!("{:>width$} {}", "^", err.kind(), width = column + 1)
+}
+
+/// The truth table, as `opts` asks for it
+fn table(opts: &Options, tester: &Tester<Tokens>) -> String {
+    let mut out = String::new();
+    let res = match opts.format {
+        Format::Text(style) => {
+            let mut w = TableWriter::new(style);
+            w.set_rows(opts.rows);
+            w.set_values(opts.values);
+            w.write(&mut out, tester)
+        }
+        Format::Csv | Format::Tsv => {
+            let mut w = match opts.format {
+                Format::Csv => CsvWriter::csv(),
+                _ => CsvWriter::tsv(),
+            };
+            w.set_rows(opts.rows);
+            w.set_values(opts.values);
+            w.write(&mut out, tester)
+        }
+        Format::Latex => {
+            let mut w = LatexWriter::new();
+            w.set_rows(opts.rows);
+            w.set_values(opts.values);
+            w.write(&mut out, tester)
+        }
+    };
+    res.expect("Strings can always be written to");
+    out
+}
+
+/// The values of the variables as `name=value` pairs
+fn assignment(opts: &Options, vars: &[&str], vals: impl Iterator<Item = bool>) -> String {
+    vars.iter()
+        .zip(vals)
+        .map(|(name, val)| format!("{}={}", name, opts.values.spell(val)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,66 @@
+use core::fmt;
+
+/// What went wrong while parsing an expression
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ParseErrorKind {
+    /// A Left Parenthesis that is never closed
+    UnclosedParen,
+    /// A Right Parenthesis that closes nothing
+    UnopenedParen,
+    /// An operator, or the end of the input,
+    /// where a value was expected
+    MissingOperand,
+    /// A value where an operator was expected
+    MissingOperator,
+    /// A parenthesis in a notation that has no use for them
+    UnexpectedParen,
+    /// More input after a whole expression
+    TrailingInput,
+    /// An operand past the last one an operator takes
+    ExtraOperand,
+}
+
+/// An error found while parsing an expression, along
+/// with the byte offset it was found at
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ParseError {
+    offset: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub(crate) fn new(offset: usize, kind: ParseErrorKind) -> Self {
+        Self { offset, kind }
+    }
+
+    /// The byte offset into the input the error was found at,
+    /// which is the length of the input if it ended too early
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// What went wrong
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ParseErrorKind::UnclosedParen => "this paren is never closed",
+            ParseErrorKind::UnopenedParen => "this paren closes nothing",
+            ParseErrorKind::MissingOperand => "expected a value",
+            ParseErrorKind::MissingOperator => "expected an operator",
+            ParseErrorKind::UnexpectedParen => "parens are not allowed in this notation",
+            ParseErrorKind::TrailingInput => "expected the end of the expression",
+            ParseErrorKind::ExtraOperand => "this operand is one too many",
+        })
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "offset {}: {}", self.offset, self.kind)
+    }
+}
@@ -0,0 +1,517 @@
+use super::{tree::Node, Token, Tokens};
+use alloc::vec::Vec;
+
+/// The names given to variables by [`Generator::new`]
+///
+/// [`Generator::new`]: `Generator::new`
+const LETTERS: [&str; 26] = [
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s",
+    "t", "u", "v", "w", "x", "y", "z",
+];
+
+/// The operators, in the order of their weights
+const OPERATORS: [Token<'static>; 6] = [
+    Token::Not,
+    Token::And,
+    Token::Xor,
+    Token::Or,
+    Token::Implication,
+    Token::Equality,
+];
+
+/// A source of randomness, so that expressions
+/// can be generated without `std`
+pub trait Rng {
+    /// The next random number, with every bit equally likely
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// A small and fast [`Rng`], which always gives
+/// the same numbers for the same seed
+///
+/// [`Rng`]: `Rng`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Start from the given seed
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// A number below `n`, which must not be `0`
+fn below<R: Rng>(rng: &mut R, n: u64) -> u64 {
+    rng.next_u64() % n
+}
+
+/// Makes random, well formed expressions
+///
+/// Every expression is made over all of the variables, even
+/// those that happen not to appear in it, and is at most as
+/// deep as asked for, counting a lone value as depth `0`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Generator<'a> {
+    vars: &'a [&'a str],
+    depth: usize,
+    weights: [u32; 6],
+    literals: bool,
+}
+
+impl Generator<'static> {
+    /// Generate expressions over `var_count` variables, named
+    /// `a` through `z`, up to depth `4`, with every operator
+    /// equally likely
+    ///
+    /// # Panics
+    ///
+    /// When there are more than 26 variables, see
+    /// [`Generator::with_vars`] to name them instead
+    ///
+    /// [`Generator::with_vars`]: `Generator::with_vars`
+    pub fn new(var_count: usize) -> Self {
+        assert!(
+            var_count <= LETTERS.len(),
+            "Only {} variables can be named by letter",
+            LETTERS.len()
+        );
+        Self::with_vars(&LETTERS[..var_count])
+    }
+}
+
+impl<'a> Generator<'a> {
+    /// Generate expressions over the named variables
+    pub fn with_vars(vars: &'a [&'a str]) -> Self {
+        Self {
+            vars,
+            depth: 4,
+            weights: [1; 6],
+            literals: true,
+        }
+    }
+
+    /// The deepest the expressions may be
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Only use the given operators, each equally likely
+    ///
+    /// # Panics
+    ///
+    /// When any of the tokens is not an operator
+    pub fn operators(mut self, ops: &[Token]) -> Self {
+        self.weights = [0; 6];
+        for op in ops {
+            self = self.weight(*op, 1);
+        }
+        self
+    }
+
+    /// How likely an operator is compared to the others,
+    /// where `0` leaves it out
+    ///
+    /// # Panics
+    ///
+    /// When the token is not an operator
+    pub fn weight(mut self, op: Token, weight: u32) -> Self {
+        let idx = OPERATORS
+            .iter()
+            .position(|o| *o == op)
+            .expect("Only operators have weights");
+        self.weights[idx] = weight;
+        self
+    }
+
+    /// Whether `true` and `false` may appear,
+    /// which they do by default
+    pub fn literals(mut self, literals: bool) -> Self {
+        self.literals = literals;
+        self
+    }
+
+    /// The amount of variables
+    pub fn var_count(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// A random expression
+    ///
+    /// # Panics
+    ///
+    /// When there are neither variables nor literals to use
+    pub fn generate<R: Rng>(&self, mut rng: R) -> Tokens<'a> {
+        assert!(
+            !self.vars.is_empty() || self.literals,
+            "There must be variables or literals to use"
+        );
+
+        let mut toks = Vec::new();
+        self.push(&mut rng, self.depth, &mut toks);
+        Tokens::new(toks, self.vars.to_vec())
+    }
+
+    /// Push a random expression at most `depth` deep, in RPN
+    fn push<R: Rng>(&self, rng: &mut R, depth: usize, toks: &mut Vec<Token<'a>>) {
+        let total: u64 = self.weights.iter().map(|w| u64::from(*w)).sum();
+        // the outermost operator is always there, so that
+        // the depth is not mostly wasted on lone values
+        let leaf = depth == 0 || total == 0 || (depth < self.depth && below(rng, 4) == 0);
+
+        if leaf {
+            let values = self.vars.len() as u64 + if self.literals { 2 } else { 0 };
+            toks.push(match below(rng, values) as usize {
+                v if v < self.vars.len() => Token::Var(self.vars[v], v),
+                v => Token::Literal(v == self.vars.len()),
+            });
+            return;
+        }
+
+        let mut pick = below(rng, total);
+        let (op, _) = OPERATORS
+            .iter()
+            .zip(self.weights.iter())
+            .find(|(_, w)| match pick.checked_sub(u64::from(**w)) {
+                Some(rest) => {
+                    pick = rest;
+                    false
+                }
+                None => true,
+            })
+            .expect("The pick is below the total of the weights");
+
+        for _ in 0..op.arity() {
+            self.push(rng, depth - 1, toks);
+        }
+        toks.push(*op);
+    }
+}
+
+impl<'a> Tokens<'a> {
+    /// Smaller expressions over the same variables, the
+    /// smallest first, for shrinking failing test cases
+    ///
+    /// These are the literals, the operands of the outermost
+    /// operator, and the expression with one of its operands
+    /// shrunk in turn.
+    pub fn shrink(&self) -> Vec<Tokens<'a>> {
+        let vars = self.vars().to_vec();
+        shrink(&Node::from_tokens(self))
+            .into_iter()
+            .map(|n| n.to_tokens(vars.clone()))
+            .collect()
+    }
+}
+
+fn shrink<'a>(node: &Node<'a>) -> Vec<Node<'a>> {
+    let mut out = Vec::new();
+    match node {
+        Node::Lit(false) => {}
+        Node::Lit(true) => out.push(Node::Lit(false)),
+        Node::Var(..) => {
+            out.push(Node::Lit(false));
+            out.push(Node::Lit(true));
+        }
+        Node::Not(v) => {
+            out.push(Node::Lit(false));
+            out.push(Node::Lit(true));
+            out.push((**v).clone());
+            for v in shrink(v) {
+                out.push(Node::not(v));
+            }
+        }
+        Node::Op(t, l, r) => {
+            out.push(Node::Lit(false));
+            out.push(Node::Lit(true));
+            out.push((**l).clone());
+            out.push((**r).clone());
+            for l in shrink(l) {
+                out.push(Node::op(*t, l, (**r).clone()));
+            }
+            for r in shrink(r) {
+                out.push(Node::op(*t, (**l).clone(), r));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(feature = "proptest")]
+mod strategy {
+    use super::{Generator, Rng, Tokens};
+    use alloc::vec::Vec;
+    use core::mem;
+    use proptest::{
+        strategy::{NewTree, Strategy, ValueTree},
+        test_runner::{TestRng, TestRunner},
+    };
+
+    impl Rng for TestRng {
+        fn next_u64(&mut self) -> u64 {
+            proptest::prelude::Rng::next_u64(self)
+        }
+    }
+
+    impl Strategy for Generator<'static> {
+        type Tree = ShrinkTree;
+        type Value = Tokens<'static>;
+
+        fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+            Ok(ShrinkTree::new(self.generate(runner.rng())))
+        }
+    }
+
+    /// A generated expression, shrunk through [`Tokens::shrink`]
+    ///
+    /// [`Tokens::shrink`]: `Tokens::shrink`
+    #[derive(Debug, Clone)]
+    pub struct ShrinkTree {
+        current: Tokens<'static>,
+        /// the shrinks of `current` not tried yet, the last first
+        untried: Vec<Tokens<'static>>,
+        /// what was current before the last simplification
+        prev: Option<(Tokens<'static>, Vec<Tokens<'static>>)>,
+    }
+
+    impl ShrinkTree {
+        fn new(current: Tokens<'static>) -> Self {
+            let mut untried = current.shrink();
+            untried.reverse();
+            Self {
+                current,
+                untried,
+                prev: None,
+            }
+        }
+    }
+
+    impl ValueTree for ShrinkTree {
+        type Value = Tokens<'static>;
+
+        fn current(&self) -> Tokens<'static> {
+            self.current.clone()
+        }
+
+        fn simplify(&mut self) -> bool {
+            match self.untried.pop() {
+                Some(next) => {
+                    let next = Self::new(next);
+                    let prev = mem::replace(&mut self.current, next.current);
+                    let rest = mem::replace(&mut self.untried, next.untried);
+                    self.prev = Some((prev, rest));
+                    true
+                }
+                None => false,
+            }
+        }
+
+        fn complicate(&mut self) -> bool {
+            match self.prev.take() {
+                Some((prev, untried)) => {
+                    self.current = prev;
+                    self.untried = untried;
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::ShrinkTree;
+        use crate::parsing::Parser;
+        use alloc::string::{String, ToString};
+        use proptest::strategy::ValueTree;
+
+        fn current(tree: &ShrinkTree) -> String {
+            tree.current().to_string()
+        }
+
+        #[test]
+        fn simplifies_and_complicates() {
+            let mut tree = ShrinkTree::new(Parser::parse("a & b").shunting_yard());
+
+            // false is tried first, and has nothing smaller
+            assert!(tree.simplify());
+            assert_eq!(current(&tree), "false");
+            assert!(!tree.simplify());
+            assert_eq!(current(&tree), "false");
+
+            // so it is undone, once, to try the next shrink
+            assert!(tree.complicate());
+            assert_eq!(current(&tree), "a & b");
+            assert!(!tree.complicate());
+            assert!(tree.simplify());
+            assert_eq!(current(&tree), "true");
+
+            // which is shrunk in turn
+            assert!(tree.simplify());
+            assert_eq!(current(&tree), "false");
+            assert!(tree.complicate());
+            assert_eq!(current(&tree), "true");
+            assert!(!tree.simplify());
+        }
+
+        #[test]
+        fn tries_every_shrink_in_order() {
+            let tokens = Parser::parse("!a | b").shunting_yard();
+            let mut tree = ShrinkTree::new(tokens.clone());
+            for shrink in tokens.shrink() {
+                assert!(tree.simplify());
+                assert_eq!(tree.current(), shrink);
+                assert!(tree.complicate());
+                assert_eq!(tree.current(), tokens);
+            }
+            assert!(!tree.simplify());
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+pub use strategy::ShrinkTree;
+
+#[cfg(feature = "quickcheck")]
+mod arbitrary {
+    use super::{Generator, Rng, Tokens};
+    use alloc::boxed::Box;
+    use quickcheck::{Arbitrary, Gen};
+
+    struct GenRng<'g>(&'g mut Gen);
+
+    impl<'g> Rng for GenRng<'g> {
+        fn next_u64(&mut self) -> u64 {
+            u64::arbitrary(self.0)
+        }
+    }
+
+    /// Expressions over up to 8 variables, as deep
+    /// as the size of the [`Gen`] allows, up to `6`
+    ///
+    /// [`Gen`]: `Gen`
+    impl Arbitrary for Tokens<'static> {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let vars = (g.size() / 8).clamp(1, 8);
+            let depth = (g.size() / 16).clamp(1, 6);
+            Generator::new(vars).depth(depth).generate(GenRng(g))
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            Box::new(Tokens::shrink(self).into_iter())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Generator, SplitMix64, OPERATORS};
+    use crate::parsing::{Token, Tokens};
+    use alloc::{vec, vec::Vec};
+
+    /// How many operators deep the expression is
+    fn depth(tokens: &Tokens) -> usize {
+        tokens.fold(|_, args: Vec<usize>| args.iter().map(|d| d + 1).max().unwrap_or(0))
+    }
+
+    /// How far the expression is from `false`, which every
+    /// shrink has to bring it closer to
+    fn size(tokens: &Tokens) -> usize {
+        tokens.fold(|t, args: Vec<usize>| {
+            let own = match t {
+                Token::Literal(b) => b as usize,
+                Token::Var(..) => 2,
+                _ => 3,
+            };
+            own + args.iter().sum::<usize>()
+        })
+    }
+
+    fn contains(tokens: &Tokens, op: Token) -> bool {
+        tokens.fold(|t, args: Vec<bool>| t == op || args.contains(&true))
+    }
+
+    #[test]
+    fn respects_the_depth() {
+        for max in 0..6 {
+            let gen = Generator::new(3).depth(max);
+            for seed in 0..200 {
+                let tokens = gen.generate(SplitMix64::new(seed));
+                assert!(depth(&tokens) <= max, "{} is deeper than {}", tokens, max);
+                assert_eq!(tokens.var_count(), 3);
+            }
+        }
+    }
+
+    #[test]
+    fn leaves_out_zero_weights() {
+        let gen = Generator::new(3)
+            .weight(Token::Xor, 0)
+            .weight(Token::Implication, 0)
+            .literals(false);
+        let only_and = Generator::new(3).operators(&[Token::And]);
+        for seed in 0..200 {
+            let tokens = gen.generate(SplitMix64::new(seed));
+            assert!(!contains(&tokens, Token::Xor), "{}", tokens);
+            assert!(!contains(&tokens, Token::Implication), "{}", tokens);
+            assert!(!contains(&tokens, Token::Literal(true)), "{}", tokens);
+            assert!(!contains(&tokens, Token::Literal(false)), "{}", tokens);
+
+            let tokens = only_and.generate(SplitMix64::new(seed));
+            for op in OPERATORS.iter().filter(|op| **op != Token::And) {
+                assert!(!contains(&tokens, *op), "{}", tokens);
+            }
+        }
+    }
+
+    #[test]
+    fn seeds_are_deterministic() {
+        let gen = Generator::new(4).depth(5);
+        for seed in 0..50 {
+            assert_eq!(
+                gen.generate(SplitMix64::new(seed)),
+                gen.generate(SplitMix64::new(seed))
+            );
+        }
+        let differ = (0..50)
+            .filter(|s| gen.generate(SplitMix64::new(*s)) != gen.generate(SplitMix64::new(s + 50)))
+            .count();
+        assert!(differ > 0);
+    }
+
+    #[test]
+    fn shrinks_are_strictly_smaller() {
+        let gen = Generator::new(3).depth(4);
+        for seed in 0..100 {
+            let tokens = gen.generate(SplitMix64::new(seed));
+            for shrink in tokens.shrink() {
+                assert!(
+                    size(&shrink) < size(&tokens),
+                    "{} does not shrink to {}",
+                    tokens,
+                    shrink
+                );
+                assert_eq!(shrink.vars(), tokens.vars());
+            }
+        }
+        assert!(Tokens::new(vec![Token::Literal(false)], vec![])
+            .shrink()
+            .is_empty());
+    }
+}
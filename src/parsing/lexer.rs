@@ -6,8 +6,11 @@ use core::{iter::Peekable, str::SplitWhitespace};
 #[derive(Debug)]
 pub struct Lexer<'i> {
     literals: TokenLiterals,
+    source: &'i str,
     input: Peekable<SplitWhitespace<'i>>,
     curr_str: &'i str,
+    offset: usize,
+    end: usize,
     var_map: BTreeMap<&'i str, usize>,
 }
 
@@ -17,30 +20,48 @@ impl<'i> Lexer<'i> {
     ///
     /// [`TokenLiterals`]: `TokenLiterals`
     pub fn lex(input: &'i str) -> Self {
-        let mut input = input.split_whitespace().peekable();
-        let curr_str = input.next().unwrap_or("");
-        Self {
-            input,
-            curr_str,
-            literals: TokenLiterals::default(),
-            var_map: BTreeMap::new(),
-        }
+        Self::lex_with_literals(input, TokenLiterals::default())
     }
 
     /// Create a Lexer with the given [`TokenLiterals`]
     ///
     /// [`TokenLiterals`]: `TokenLiterals`
     pub fn lex_with_literals(input: &'i str, literals: TokenLiterals) -> Self {
+        let source = input;
         let mut input = input.split_whitespace().peekable();
         let curr_str = input.next().unwrap_or("");
         Self {
             literals,
+            source,
             input,
             curr_str,
+            offset: 0,
+            end: 0,
             var_map: BTreeMap::new(),
         }
     }
 
+    /// The byte offset into the input of the
+    /// last [`Token`] returned
+    ///
+    /// [`Token`]: `Token`
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The byte offset into the input just
+    /// after the last [`Token`] returned
+    ///
+    /// [`Token`]: `Token`
+    pub(crate) fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The length of the input in bytes
+    pub(crate) fn source_len(&self) -> usize {
+        self.source.len()
+    }
+
     /// Returns a Vec of var_names indexed by the order
     /// in which they appear in the expression
     ///
@@ -68,12 +89,14 @@ impl<'i> Iterator for Lexer<'i> {
                     .next()
                     .expect("curr_str was empty, and next was None, but iter didn't finsh?!?");
             }
+            // every token starts at the front of the current string
+            self.offset = self.curr_str.as_ptr() as usize - self.source.as_ptr() as usize;
 
             let mut found_idx = 0;
             let mut found_val = None;
             let mut found_val_len = 0;
 
-            for i in 0..self.curr_str.len() {
+            for (i, _) in self.curr_str.char_indices() {
                 let search_str = &self.curr_str[i..];
                 if let Some((val, len)) = self.literals.starts_with(search_str) {
                     found_idx = i;
@@ -88,6 +111,7 @@ impl<'i> Iterator for Lexer<'i> {
                 // get the variable name and index
                 let name = &self.curr_str[..found_idx];
                 let idx = self.var_map.len();
+                self.end = self.offset + name.len();
                 // update our string with the remaining values
                 self.curr_str = &self.curr_str[found_idx..];
                 // return the variable
@@ -96,6 +120,7 @@ impl<'i> Iterator for Lexer<'i> {
                 // we found a value
                 // update the current string
                 self.curr_str = &self.curr_str[found_val_len..];
+                self.end = self.offset + found_val_len;
                 // return the found value
                 found_val
             } else {
@@ -103,6 +128,7 @@ impl<'i> Iterator for Lexer<'i> {
                 // get the variable name and index
                 let name = self.curr_str;
                 let idx = self.var_map.len();
+                self.end = self.offset + name.len();
                 // set our string to empty as we've taken all of it
                 self.curr_str = "";
                 // return our variable
@@ -111,3 +137,43 @@ impl<'i> Iterator for Lexer<'i> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Lexer;
+    use crate::parsing::{Token, TokenLiterals};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn lexes_multi_byte_spellings() {
+        let literals = TokenLiterals::profile("math-unicode").expect("known profile");
+        let toks: Vec<Token> = Lexer::lex_with_literals("a ∧ b", literals).collect();
+        assert_eq!(toks, [Token::Var("a", 0), Token::And, Token::Var("b", 1)]);
+
+        let toks: Vec<Token> = Lexer::lex("¬a∨b").collect();
+        assert_eq!(
+            toks,
+            [
+                Token::Not,
+                Token::Var("a", 0),
+                Token::Or,
+                Token::Var("b", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn lexes_multi_byte_variables() {
+        let toks: Vec<Token> = Lexer::lex("é∧ü & é").collect();
+        assert_eq!(
+            toks,
+            [
+                Token::Var("é", 0),
+                Token::And,
+                Token::Var("ü", 1),
+                Token::And,
+                Token::Var("é", 0)
+            ]
+        );
+    }
+}
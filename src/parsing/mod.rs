@@ -1,7 +1,18 @@
+mod error;
+mod generate;
 mod lexer;
 mod parser;
+mod simplify;
 mod tokens;
+pub(crate) mod tree;
 
+pub use error::{ParseError, ParseErrorKind};
+#[cfg(feature = "proptest")]
+pub use generate::ShrinkTree;
+pub use generate::{Generator, Rng, SplitMix64};
 pub use lexer::Lexer;
-pub use parser::Parser;
-pub use tokens::{Token, TokenLiterals, Tokens};
+pub use parser::{Notation, Parser};
+pub use simplify::{Law, Simplification, Step};
+pub use tokens::{
+    OwnedTokens, ProfileError, ProfileErrorKind, SpellingError, Token, TokenLiterals, Tokens,
+};
@@ -1,5 +1,20 @@
-use super::{Lexer, Token, TokenLiterals, Tokens};
+use super::{Lexer, ParseError, ParseErrorKind, Token, TokenLiterals, Tokens};
 use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The ways a Boolean Expression can be written
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Notation {
+    /// Operators between their operands, `a & (b | !c)`
+    Infix,
+    /// Polish notation, operators before their operands, `& a | b ! c`
+    Prefix,
+    /// Reverse Polish notation, operators after their operands, `a b c ! | &`
+    Postfix,
+    /// Lisp style S-expressions, `(and a (or b (not c)))`, where
+    /// the binary operators take one or more operands
+    SExpression,
+}
 
 /// Boolean Expression Parser
 #[derive(Debug)]
@@ -21,52 +36,322 @@ impl<'i> Parser<'i> {
     }
 
     pub fn shunting_yard(&mut self) -> Tokens<'i> {
+        self.try_shunting_yard()
+            .unwrap_or_else(|e| panic!("Invalid expression at {}!", e))
+    }
+
+    /// Parse an infix expression, returning where and
+    /// why it is malformed instead of panicking
+    pub fn try_shunting_yard(&mut self) -> Result<Tokens<'i>, ParseError> {
+        self.try_shunting_yard_spans().map(|(toks, _)| toks)
+    }
+
+    /// Parse an infix expression, along with where every token
+    /// of the result is in the input, as the span of the whole
+    /// subexpression it ends and the span of the token itself.
+    /// Parenthesis are only spanned by the operators around them.
+    pub(crate) fn try_shunting_yard_spans(&mut self) -> Result<(Tokens<'i>, Spans), ParseError> {
+        use ParseErrorKind::*;
         use Token::*;
-        let mut toks: Vec<Token> = Vec::new();
-        let mut stack: Vec<Token> = Vec::new();
+        let mut out = Output::default();
+        // operators waiting on their right operand, along
+        // with where they were found in the input
+        let mut stack: Vec<(Token, Range<usize>)> = Vec::new();
+        // whether the next token has to start a value,
+        // that is a value, a Not or a Left Parenthesis
+        let mut operand = true;
 
         while let Some(t) = self.lexer.next() {
+            let at = self.lexer.offset()..self.lexer.end();
+            let starts_value = matches!(t, Var(..) | Literal(..) | Not | LParen);
+            if starts_value != operand {
+                let kind = if operand {
+                    MissingOperand
+                } else {
+                    MissingOperator
+                };
+                return Err(ParseError::new(at.start, kind));
+            }
+
             match t {
                 // Values can go directly to the output
-                v @ Var(..) | v @ Literal(..) => toks.push(v),
+                v @ Var(..) | v @ Literal(..) => {
+                    out.push(v, at);
+                    operand = false;
+                }
                 // Parens mess mostly with the stack
-                LParen => stack.push(LParen),
+                LParen => stack.push((LParen, at)),
                 RParen => {
-                    let mut found = false;
-                    while let Some(tok) = stack.pop() {
+                    let mut open = None;
+                    while let Some((tok, tok_at)) = stack.pop() {
                         if tok == LParen {
-                            found = true;
+                            open = Some(tok_at.start);
                             break;
                         }
 
-                        toks.push(tok);
+                        out.push(tok, tok_at);
                     }
 
-                    if found == false {
-                        panic!("Mismached parens!");
+                    match open {
+                        // the group spans its parenthesis
+                        Some(start) => {
+                            let value = out.values.last_mut().expect("Groups are not empty");
+                            value.start = start;
+                            value.end = at.end;
+                        }
+                        None => return Err(ParseError::new(at.start, UnopenedParen)),
                     }
                 }
                 t => {
-                    while let Some(tok) = stack.pop() {
-                        if tok.precedence() > t.precedence() {
-                            toks.push(tok);
+                    while let Some((tok, tok_at)) = stack.pop() {
+                        // a Left Parenthesis is never an operand,
+                        // it only ever gets removed by its pair
+                        if tok != LParen && tok.precedence() > t.precedence() {
+                            out.push(tok, tok_at);
                         } else {
-                            stack.push(tok);
+                            stack.push((tok, tok_at));
                             break;
                         }
                     }
-                    stack.push(t);
+                    stack.push((t, at));
+                    operand = true;
                 }
             }
         }
 
-        while let Some(t) = stack.pop() {
-            if t == LParen || t == RParen {
-                panic!("Mismached Parens!");
+        if operand {
+            return Err(ParseError::new(self.lexer.source_len(), MissingOperand));
+        }
+        while let Some((t, at)) = stack.pop() {
+            if t == LParen {
+                return Err(ParseError::new(at.start, UnclosedParen));
+            }
+            out.push(t, at);
+        }
+
+        Ok((Tokens::new(out.toks, self.lexer.var_map()), out.spans))
+    }
+
+    /// Parse the expression written in the given [`Notation`]
+    ///
+    /// [`Notation`]: `Notation`
+    pub fn parse_notation(&mut self, notation: Notation) -> Tokens<'i> {
+        self.try_parse_notation(notation)
+            .unwrap_or_else(|e| panic!("Invalid expression at {}!", e))
+    }
+
+    /// Parse the expression written in the given [`Notation`],
+    /// returning where and why it is malformed instead of panicking
+    ///
+    /// [`Notation`]: `Notation`
+    pub fn try_parse_notation(&mut self, notation: Notation) -> Result<Tokens<'i>, ParseError> {
+        match notation {
+            Notation::Infix => self.try_shunting_yard(),
+            Notation::Prefix => self.try_prefix(),
+            Notation::Postfix => self.try_postfix(),
+            Notation::SExpression => self.try_s_expression(),
+        }
+    }
+
+    /// Parse an expression in Polish notation, where every
+    /// operator comes before its operands
+    pub fn prefix(&mut self) -> Tokens<'i> {
+        self.try_prefix()
+            .unwrap_or_else(|e| panic!("Invalid expression at {}!", e))
+    }
+
+    /// Parse an expression in Polish notation, returning where
+    /// and why it is malformed instead of panicking
+    pub fn try_prefix(&mut self) -> Result<Tokens<'i>, ParseError> {
+        let input = self.located();
+        let mut toks = Vec::with_capacity(input.len());
+        let mut rest = input.as_slice();
+
+        push_prefix(&mut rest, &mut toks, self.lexer.source_len())?;
+        if let Some((_, at)) = rest.first() {
+            return Err(ParseError::new(*at, ParseErrorKind::TrailingInput));
+        }
+
+        Ok(Tokens::new(toks, self.lexer.var_map()))
+    }
+
+    /// Parse an expression in Reverse Polish notation,
+    /// where every operator comes after its operands
+    pub fn postfix(&mut self) -> Tokens<'i> {
+        self.try_postfix()
+            .unwrap_or_else(|e| panic!("Invalid expression at {}!", e))
+    }
+
+    /// Parse an expression in Reverse Polish notation, returning
+    /// where and why it is malformed instead of panicking
+    pub fn try_postfix(&mut self) -> Result<Tokens<'i>, ParseError> {
+        use ParseErrorKind::*;
+        let mut toks = Vec::new();
+        let mut depth = 0;
+
+        for (t, at) in self.located() {
+            if t == Token::LParen || t == Token::RParen {
+                return Err(ParseError::new(at, UnexpectedParen));
+            }
+            if depth < t.arity() {
+                return Err(ParseError::new(at, MissingOperand));
             }
+            depth = depth - t.arity() + 1;
             toks.push(t);
         }
 
-        Tokens::new(toks, self.lexer.var_map())
+        let end = self.lexer.source_len();
+        match depth {
+            0 => Err(ParseError::new(end, MissingOperand)),
+            1 => Ok(Tokens::new(toks, self.lexer.var_map())),
+            _ => Err(ParseError::new(end, MissingOperator)),
+        }
+    }
+
+    /// Parse an expression written as S-expressions, where
+    /// every operator comes first in its own list
+    pub fn s_expression(&mut self) -> Tokens<'i> {
+        self.try_s_expression()
+            .unwrap_or_else(|e| panic!("Invalid expression at {}!", e))
+    }
+
+    /// Parse an expression written as S-expressions, returning
+    /// where and why it is malformed instead of panicking
+    pub fn try_s_expression(&mut self) -> Result<Tokens<'i>, ParseError> {
+        let input = self.located();
+        let mut toks = Vec::with_capacity(input.len());
+        let mut rest = input.as_slice();
+
+        push_s_expression(&mut rest, &mut toks, self.lexer.source_len())?;
+        if let Some((_, at)) = rest.first() {
+            return Err(ParseError::new(*at, ParseErrorKind::TrailingInput));
+        }
+
+        Ok(Tokens::new(toks, self.lexer.var_map()))
+    }
+
+    /// Every token left, along with the offset it starts at
+    fn located(&mut self) -> Vec<(Token<'i>, usize)> {
+        let mut out = Vec::new();
+        while let Some(t) = self.lexer.next() {
+            out.push((t, self.lexer.offset()));
+        }
+        out
+    }
+}
+
+/// Where each token is, as the span of the subexpression
+/// it ends followed by the span of the token itself
+pub(crate) type Spans = Vec<(Range<usize>, Range<usize>)>;
+
+/// The output of the Shunting Yard, along with where its tokens are
+#[derive(Default)]
+struct Output<'i> {
+    toks: Vec<Token<'i>>,
+    spans: Spans,
+    /// the spans of the values the tokens so far leave
+    values: Vec<Range<usize>>,
+}
+
+impl<'i> Output<'i> {
+    /// Add a token found at `at`, which for an
+    /// operator spans its operands as well
+    fn push(&mut self, tok: Token<'i>, at: Range<usize>) {
+        let span = match tok.arity() {
+            0 => at.clone(),
+            arity => {
+                let right = self.values.pop().expect("Operators follow their operands");
+                let start = if arity == 1 {
+                    at.start
+                } else {
+                    self.values
+                        .pop()
+                        .expect("Operators follow their operands")
+                        .start
+                };
+                start..right.end
+            }
+        };
+        self.values.push(span.clone());
+        self.spans.push((span, at));
+        self.toks.push(tok);
+    }
+}
+
+/// Move a single prefix expression from the front of
+/// `input` to `toks`, in postfix order, where `end` is
+/// the offset of the end of the input
+fn push_prefix<'i>(
+    input: &mut &[(Token<'i>, usize)],
+    toks: &mut Vec<Token<'i>>,
+    end: usize,
+) -> Result<(), ParseError> {
+    let (&(t, at), rest) = input
+        .split_first()
+        .ok_or_else(|| ParseError::new(end, ParseErrorKind::MissingOperand))?;
+    *input = rest;
+    if t == Token::LParen || t == Token::RParen {
+        return Err(ParseError::new(at, ParseErrorKind::UnexpectedParen));
+    }
+
+    for _ in 0..t.arity() {
+        push_prefix(input, toks, end)?;
+    }
+    toks.push(t);
+    Ok(())
+}
+
+/// Move a single S-expression from the front of
+/// `input` to `toks`, in postfix order, where `end`
+/// is the offset of the end of the input
+fn push_s_expression<'i>(
+    input: &mut &[(Token<'i>, usize)],
+    toks: &mut Vec<Token<'i>>,
+    end: usize,
+) -> Result<(), ParseError> {
+    use ParseErrorKind::*;
+    use Token::*;
+
+    let (&(t, at), rest) = input
+        .split_first()
+        .ok_or_else(|| ParseError::new(end, MissingOperand))?;
+    *input = rest;
+    match t {
+        v @ Var(..) | v @ Literal(..) => toks.push(v),
+        LParen => {
+            let (&(op, op_at), rest) = input
+                .split_first()
+                .ok_or_else(|| ParseError::new(at, UnclosedParen))?;
+            *input = rest;
+            if op.arity() == 0 {
+                return Err(ParseError::new(op_at, MissingOperator));
+            }
+
+            let mut count = 0;
+            let close = loop {
+                match input.first() {
+                    None => return Err(ParseError::new(at, UnclosedParen)),
+                    Some(&(RParen, close)) => break close,
+                    Some(&(_, operand)) if op == Not && count == 1 => {
+                        return Err(ParseError::new(operand, ExtraOperand))
+                    }
+                    Some(_) => {
+                        push_s_expression(input, toks, end)?;
+                        count += 1;
+                    }
+                }
+            };
+            *input = &input[1..];
+
+            match (op, count) {
+                (_, 0) => return Err(ParseError::new(close, MissingOperand)),
+                (Not, _) => toks.push(Not),
+                // chains associate to the right, like in infix
+                (op, _) => toks.extend((1..count).map(|_| op)),
+            }
+        }
+        RParen => return Err(ParseError::new(at, UnopenedParen)),
+        _ => return Err(ParseError::new(at, MissingOperand)),
     }
+    Ok(())
 }
@@ -0,0 +1,445 @@
+use super::{tree::Node, Token, Tokens};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The laws of Boolean algebra the simplifier knows about
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Law {
+    /// `a ∧ true ≡ a`, `a ∨ false ≡ a`, `a ⊕ false ≡ a`, `a ≡ true ≡ a`
+    Identity,
+    /// `a ∧ false ≡ false`, `a ∨ true ≡ true`
+    Domination,
+    /// `a ∧ a ≡ a`, `a ∨ a ≡ a`
+    Idempotence,
+    /// `a ∧ ¬a ≡ false`, `a ∨ ¬a ≡ true`, `¬true ≡ false`, `¬false ≡ true`
+    Complement,
+    /// `¬¬a ≡ a`
+    DoubleNegation,
+    /// `¬(a ∧ b) ≡ ¬a ∨ ¬b`, `¬(a ∨ b) ≡ ¬a ∧ ¬b`
+    DeMorgan,
+    /// `a ∧ (a ∨ b) ≡ a`, `a ∨ (a ∧ b) ≡ a`
+    Absorption,
+    /// `(a ∧ b) ∨ (a ∧ c) ≡ a ∧ (b ∨ c)`, `(a ∨ b) ∧ (a ∨ c) ≡ a ∨ (b ∧ c)`
+    Distribution,
+    /// `a → b ≡ ¬a ∨ b`
+    ImplicationElimination,
+    /// `(a ∧ b) ∨ (¬a ∧ c) ∨ (b ∧ c) ≡ (a ∧ b) ∨ (¬a ∧ c)` and its dual
+    Consensus,
+}
+
+impl Law {
+    /// The name of the law
+    pub fn name(&self) -> &'static str {
+        match self {
+            Law::Identity => "identity",
+            Law::Domination => "domination",
+            Law::Idempotence => "idempotence",
+            Law::Complement => "complement",
+            Law::DoubleNegation => "double negation",
+            Law::DeMorgan => "De Morgan",
+            Law::Absorption => "absorption",
+            Law::Distribution => "distribution",
+            Law::ImplicationElimination => "implication elimination",
+            Law::Consensus => "consensus",
+        }
+    }
+}
+
+impl fmt::Display for Law {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A single rewrite of an expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<'a> {
+    law: Law,
+    before: Tokens<'a>,
+    after: Tokens<'a>,
+}
+
+impl<'a> Step<'a> {
+    /// The law that was applied
+    pub fn law(&self) -> Law {
+        self.law
+    }
+
+    /// The whole expression before the law was applied
+    pub fn before(&self) -> &Tokens<'a> {
+        &self.before
+    }
+
+    /// The whole expression after the law was applied
+    pub fn after(&self) -> &Tokens<'a> {
+        &self.after
+    }
+
+    /// Whether both sides of the step have the same truth table
+    #[cfg(feature = "tester")]
+    pub fn is_equivalent(&self) -> bool {
+        use crate::eval::Tester;
+
+        // both sides share the same variables, even if some
+        // no longer appear, so their tables line up
+        Tester::with_tokens(self.before.clone()).truth_table()
+            == Tester::with_tokens(self.after.clone()).truth_table()
+    }
+}
+
+impl<'a> fmt::Display for Step<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}  ≡  {}    ({})", self.before, self.after, self.law)
+    }
+}
+
+/// The result of simplifying an expression, along
+/// with every step taken to get there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simplification<'a> {
+    steps: Vec<Step<'a>>,
+    result: Tokens<'a>,
+}
+
+impl<'a> Simplification<'a> {
+    /// The steps taken, in order
+    pub fn steps(&self) -> &[Step<'a>] {
+        &self.steps
+    }
+
+    /// The simplified expression
+    pub fn result(&self) -> &Tokens<'a> {
+        &self.result
+    }
+
+    /// Take the simplified expression
+    pub fn into_result(self) -> Tokens<'a> {
+        self.result
+    }
+
+    /// Check every step against the truth tables of its sides,
+    /// returning the first one that does not hold
+    #[cfg(feature = "tester")]
+    pub fn verify(&self) -> Result<(), &Step<'a>> {
+        match self.steps.iter().find(|s| !s.is_equivalent()) {
+            Some(s) => Err(s),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Tokens<'a> {
+    /// The most rewrites [`simplify`] will perform
+    ///
+    /// [`simplify`]: `Tokens::simplify`
+    pub const MAX_SIMPLIFY_STEPS: usize = 1024;
+
+    /// Rewrite the expression with the laws of Boolean algebra
+    /// until none of them apply anymore, keeping track of
+    /// every step.
+    ///
+    /// The outermost sub expression a law applies to is always
+    /// rewritten first. The variables of the expression are kept,
+    /// even if they no longer appear in the result.
+    pub fn simplify(&self) -> Simplification<'a> {
+        let vars = self.vars().to_vec();
+        let mut tree = Node::from_tokens(self);
+        let mut before = self.clone();
+        let mut steps = Vec::new();
+
+        while steps.len() < Self::MAX_SIMPLIFY_STEPS {
+            match rewrite(&tree) {
+                Some((law, next)) => {
+                    let after = next.to_tokens(vars.clone());
+                    steps.push(Step {
+                        law,
+                        before,
+                        after: after.clone(),
+                    });
+                    before = after;
+                    tree = next;
+                }
+                None => break,
+            }
+        }
+
+        Simplification {
+            steps,
+            result: before,
+        }
+    }
+}
+
+/// Apply a single law to the outermost sub expression it fits
+fn rewrite<'a>(node: &Node<'a>) -> Option<(Law, Node<'a>)> {
+    if let Some(v) = apply(node) {
+        return Some(v);
+    }
+
+    match node {
+        Node::Lit(_) | Node::Var(..) => None,
+        Node::Not(v) => rewrite(v).map(|(law, v)| (law, Node::not(v))),
+        Node::Op(t, l, r) => {
+            if let Some((law, l)) = rewrite(l) {
+                Some((law, Node::op(*t, l, (**r).clone())))
+            } else {
+                rewrite(r).map(|(law, r)| (law, Node::op(*t, (**l).clone(), r)))
+            }
+        }
+    }
+}
+
+/// Apply a single law to the root of the given expression
+fn apply<'a>(node: &Node<'a>) -> Option<(Law, Node<'a>)> {
+    use Token::*;
+
+    match node {
+        Node::Lit(_) | Node::Var(..) => None,
+        Node::Not(v) => match &**v {
+            Node::Lit(b) => Some((Law::Complement, Node::Lit(!b))),
+            Node::Not(v) => Some((Law::DoubleNegation, (**v).clone())),
+            Node::Op(t @ And, l, r) | Node::Op(t @ Or, l, r) => Some((
+                Law::DeMorgan,
+                Node::op(dual(*t), Node::not((**l).clone()), Node::not((**r).clone())),
+            )),
+            _ => None,
+        },
+        Node::Op(Implication, l, r) => Some((
+            Law::ImplicationElimination,
+            Node::op(Or, Node::not((**l).clone()), (**r).clone()),
+        )),
+        Node::Op(t @ Xor, l, r) | Node::Op(t @ Equality, l, r) => {
+            // false is the identity of XOR, true that of EQUALITY
+            let identity = Node::Lit(*t == Equality);
+            if **r == identity {
+                Some((Law::Identity, (**l).clone()))
+            } else if **l == identity {
+                Some((Law::Identity, (**r).clone()))
+            } else {
+                None
+            }
+        }
+        Node::Op(t, ..) => apply_chain(*t, &node.chain(*t)),
+    }
+}
+
+/// Apply a single law to a chain of ANDs or ORs
+fn apply_chain<'a>(op: Token<'a>, terms: &[&Node<'a>]) -> Option<(Law, Node<'a>)> {
+    let identity = Node::Lit(op == Token::And);
+    let dominant = Node::Lit(op != Token::And);
+    let without = |skip: usize| -> Node<'a> {
+        let rest = terms
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != skip)
+            .map(|(_, t)| (*t).clone())
+            .collect();
+        Node::from_chain(op, rest)
+    };
+    let pairs = || {
+        (0..terms.len()).flat_map(move |i| {
+            (0..terms.len())
+                .filter(move |&j| j != i)
+                .map(move |j| (i, j))
+        })
+    };
+
+    if terms.iter().any(|t| **t == dominant) {
+        return Some((Law::Domination, dominant));
+    }
+    if let Some(i) = terms.iter().position(|t| **t == identity) {
+        return Some((Law::Identity, without(i)));
+    }
+    for (i, j) in pairs().filter(|(i, j)| i < j) {
+        if terms[i] == terms[j] {
+            return Some((Law::Idempotence, without(j)));
+        }
+    }
+    for (i, j) in pairs() {
+        if let Node::Not(v) = terms[j] {
+            if **v == *terms[i] {
+                return Some((Law::Complement, dominant));
+            }
+        }
+    }
+    for (i, j) in pairs() {
+        // a term made redundant by a smaller one
+        if terms[j].chain(dual(op)).contains(&terms[i]) {
+            return Some((Law::Absorption, without(j)));
+        }
+    }
+    for (i, j) in pairs() {
+        let (ti, tj) = (terms[i].chain(dual(op)), terms[j].chain(dual(op)));
+        for k in (0..terms.len()).filter(|&k| k != i && k != j) {
+            if consensus(&ti, &tj, &terms[k].chain(dual(op))) {
+                return Some((Law::Consensus, without(k)));
+            }
+        }
+    }
+    for (i, j) in pairs().filter(|(i, j)| i < j) {
+        let (ti, tj) = (terms[i].chain(dual(op)), terms[j].chain(dual(op)));
+        if ti.len() < 2 || tj.len() < 2 {
+            continue;
+        }
+        if let Some(common) = ti.iter().find(|t| tj.contains(t)) {
+            let rest = |chain: &[&Node<'a>]| -> Node<'a> {
+                let pos = chain.iter().position(|t| t == common).unwrap();
+                let rest = chain
+                    .iter()
+                    .enumerate()
+                    .filter(|(n, _)| *n != pos)
+                    .map(|(_, t)| (*t).clone())
+                    .collect();
+                Node::from_chain(dual(op), rest)
+            };
+            let factored = Node::op(
+                dual(op),
+                (*common).clone(),
+                Node::op(op, rest(&ti), rest(&tj)),
+            );
+
+            let mut out: Vec<Node<'a>> = Vec::new();
+            for (n, t) in terms.iter().enumerate() {
+                if n == i {
+                    out.push(factored.clone());
+                } else if n != j {
+                    out.push((*t).clone());
+                }
+            }
+            return Some((Law::Distribution, Node::from_chain(op, out)));
+        }
+    }
+
+    None
+}
+
+/// Whether `c` is the consensus of `a` and `b`, that is `a` holds some
+/// `x`, `b` holds `¬x`, and `c` holds exactly the rest of both
+fn consensus(a: &[&Node], b: &[&Node], c: &[&Node]) -> bool {
+    for (n, x) in a.iter().enumerate() {
+        let neg = b.iter().position(|y| match y {
+            Node::Not(v) => **v == **x,
+            _ => false,
+        });
+        if let Some(m) = neg {
+            let rest_a = a
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != n)
+                .map(|(_, v)| *v);
+            let rest_b = b
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != m)
+                .map(|(_, v)| *v);
+            let mut rest: Vec<&Node> = Vec::new();
+            for v in rest_a.chain(rest_b) {
+                if !rest.contains(&v) {
+                    rest.push(v);
+                }
+            }
+            return !rest.is_empty() && rest.len() == c.len() && rest.iter().all(|v| c.contains(v));
+        }
+    }
+
+    false
+}
+
+/// The dual of AND is OR, and vice versa
+fn dual(t: Token) -> Token {
+    match t {
+        Token::And => Token::Or,
+        Token::Or => Token::And,
+        t => t,
+    }
+}
+
+#[cfg(all(test, feature = "tester"))]
+mod tests {
+    use super::Law::{self, *};
+    use crate::parsing::Parser;
+    use alloc::{string::ToString, vec::Vec};
+
+    /// Simplify `src`, checking that every step holds, that the
+    /// given laws were applied in order, and that it ends at `out`
+    fn assert_simplifies(src: &str, laws: &[Law], out: &str) {
+        let simplified = Parser::parse(src).shunting_yard().simplify();
+        let applied: Vec<Law> = simplified.steps().iter().map(|s| s.law()).collect();
+
+        if let Err(step) = simplified.verify() {
+            panic!("{:?}: {} does not hold", src, step);
+        }
+        assert_eq!(applied, laws, "laws applied to {:?}", src);
+        assert_eq!(simplified.result().to_string(), out, "{:?}", src);
+    }
+
+    #[test]
+    fn applies_each_law() {
+        let cases: &[(&str, Law, &str)] = &[
+            ("a & true", Identity, "a"),
+            ("true & a", Identity, "a"),
+            ("a | false", Identity, "a"),
+            ("a ^ false", Identity, "a"),
+            ("a = true", Identity, "a"),
+            ("a & false", Domination, "false"),
+            ("a | true", Domination, "true"),
+            ("a & a", Idempotence, "a"),
+            ("a | a", Idempotence, "a"),
+            ("a & !a", Complement, "false"),
+            ("a | !a", Complement, "true"),
+            ("!true", Complement, "false"),
+            ("!false", Complement, "true"),
+            ("!!a", DoubleNegation, "a"),
+            ("!(a & b)", DeMorgan, "!a | !b"),
+            ("!(a | b)", DeMorgan, "!a & !b"),
+            ("a & (a | b)", Absorption, "a"),
+            ("a | a & b", Absorption, "a"),
+            ("a & b | a & c", Distribution, "a & (b | c)"),
+            ("(a | b) & (a | c)", Distribution, "a | b & c"),
+            ("a -> b", ImplicationElimination, "!a | b"),
+            ("a & b | !a & c | b & c", Consensus, "a & b | !a & c"),
+            (
+                "(a | b) & (!a | c) & (b | c)",
+                Consensus,
+                "(a | b) & (!a | c)",
+            ),
+        ];
+        for (src, law, out) in cases {
+            assert_simplifies(src, &[*law], out);
+        }
+    }
+
+    #[test]
+    fn composes_laws() {
+        assert_simplifies("!(a & !a)", &[DeMorgan, Complement], "true");
+        assert_simplifies(
+            "!(a -> b)",
+            &[ImplicationElimination, DeMorgan, DoubleNegation],
+            "a & !b",
+        );
+        assert_simplifies(
+            "!(!a | !b) | a & b & c",
+            &[
+                DeMorgan,
+                DoubleNegation,
+                Distribution,
+                DoubleNegation,
+                Absorption,
+            ],
+            "a & b",
+        );
+    }
+
+    #[test]
+    fn keeps_simple_expressions() {
+        assert_simplifies("a & b", &[], "a & b");
+        assert_simplifies("a -> b -> c", &[ImplicationElimination; 2], "!a | !b | c");
+    }
+
+    #[test]
+    fn keeps_every_variable() {
+        let simplified = Parser::parse("a | b & !b").shunting_yard().simplify();
+        assert_eq!(simplified.result().to_string(), "a");
+        assert_eq!(simplified.result().vars(), ["a", "b"]);
+    }
+}
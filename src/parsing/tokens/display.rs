@@ -0,0 +1,96 @@
+use super::{Token, TokenLiterals, Tokens};
+use alloc::{format, string::String};
+use core::fmt;
+
+impl<'a> Tokens<'a> {
+    /// Write the expression back out in infix notation,
+    /// spelling every operator with the first of its
+    /// spellings in the given [`TokenLiterals`]
+    ///
+    /// [`TokenLiterals`]: `TokenLiterals`
+    pub fn to_string_with(&self, literals: &TokenLiterals) -> String {
+        self.infix(
+            |t| match t {
+                Token::Literal(true) => &literals.lit_true()[0],
+                Token::Literal(false) => &literals.lit_false()[0],
+                Token::Not => &literals.not()[0],
+                Token::And => &literals.and()[0],
+                Token::Xor => &literals.xor()[0],
+                Token::Or => &literals.or()[0],
+                Token::Implication => &literals.implication()[0],
+                Token::Equality => &literals.equality()[0],
+                _ => unreachable!("Only operators and literals are spelled"),
+            },
+            String::from,
+        )
+    }
+
+    /// Writes the expression in infix notation, only adding the
+    /// parenthesis required to keep the same structure
+    pub(crate) fn infix<'s>(
+        &self,
+        spell: impl Fn(Token) -> &'s str,
+        var: impl Fn(&'a str) -> String,
+    ) -> String {
+        use Token::*;
+
+        // every operand is kept along with the precedence
+        // of its outermost operator
+        let (out, _) = self.fold(|t, mut args| match t {
+            Var(name, _) => (var(name), t.precedence()),
+            Literal(_) => (spell(t).into(), t.precedence()),
+            Not => {
+                let (v, p) = args.pop().expect("Not has one operand");
+                if p < t.precedence() {
+                    (format!("{}({})", spell(t), v), t.precedence())
+                } else {
+                    (format!("{}{}", spell(t), v), t.precedence())
+                }
+            }
+            LParen | RParen => unreachable!("Parenthesis in Final Experssion"),
+            _ => {
+                let (r, rp) = args.pop().expect("Binary operators have two operands");
+                let (l, lp) = args.pop().expect("Binary operators have two operands");
+                // operators of the same precedence associate to
+                // the right, so only the left needs wrapping
+                let l = if lp <= t.precedence() {
+                    format!("({})", l)
+                } else {
+                    l
+                };
+                let r = if rp < t.precedence() {
+                    format!("({})", r)
+                } else {
+                    r
+                };
+                (format!("{} {} {}", l, spell(t), r), t.precedence())
+            }
+        });
+
+        out
+    }
+}
+
+impl<'a> fmt::Display for Tokens<'a> {
+    /// Writes the expression in infix notation using
+    /// ASCII spellings understood by the default
+    /// [`TokenLiterals`]
+    ///
+    /// [`TokenLiterals`]: `TokenLiterals`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.infix(
+            |t| match t {
+                Token::Literal(true) => "true",
+                Token::Literal(false) => "false",
+                Token::Not => "!",
+                Token::And => "&",
+                Token::Xor => "^",
+                Token::Or => "|",
+                Token::Implication => "->",
+                Token::Equality => "=",
+                _ => unreachable!("Only operators and literals are spelled"),
+            },
+            String::from,
+        ))
+    }
+}
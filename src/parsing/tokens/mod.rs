@@ -7,32 +7,105 @@ pub use token::Token;
 mod token_lit;
 pub use token_lit::TokenLiterals;
 
+mod profile;
+pub use profile::{ProfileError, ProfileErrorKind, SpellingError};
+
+mod display;
+
+mod owned;
+pub use owned::OwnedTokens;
+
 use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "owned::TokensRepr<'a>")
+)]
 pub struct Tokens<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow, rename = "tokens"))]
     toks: Vec<Token<'a>>,
+    #[cfg_attr(feature = "serde", serde(borrow, rename = "vars"))]
     var_map: Vec<&'a str>,
-    var_count: usize,
 }
 
 impl<'a> Tokens<'a> {
     pub(crate) fn new(toks: Vec<Token<'a>>, var_map: Vec<&'a str>) -> Self {
-        Tokens {
-            var_count: var_map.len(),
-            var_map,
-            toks,
-        }
+        Tokens { var_map, toks }
     }
 
-    pub fn var_at(&self, n: usize) -> &str {
+    pub fn var_at(&self, n: usize) -> &'a str {
         self.var_map[n]
     }
 
-    pub fn vars(&self) -> &[&str] {
+    pub fn vars(&self) -> &[&'a str] {
         &self.var_map
     }
 
     pub fn var_count(&self) -> usize {
-        self.var_count
+        self.var_map.len()
+    }
+
+    /// Replace the variable at index `var` with the literal
+    /// `value`, removing it from the expression's variables.
+    /// The variables after it move down by one index.
+    pub fn assign(&self, var: usize, value: bool) -> Self {
+        let toks = self
+            .toks
+            .iter()
+            .map(|t| match *t {
+                Token::Var(_, v) if v == var => Token::Literal(value),
+                Token::Var(name, v) if v > var => Token::Var(name, v - 1),
+                t => t,
+            })
+            .collect();
+        let mut var_map = self.var_map.clone();
+        var_map.remove(var);
+
+        Self::new(toks, var_map)
+    }
+
+    /// The same expression over the given variables, which
+    /// must name every variable of the expression. The
+    /// variables are indexed by their place in `vars`.
+    pub fn over_vars(&self, vars: &[&'a str]) -> Self {
+        let toks = self
+            .toks
+            .iter()
+            .map(|t| match *t {
+                Token::Var(name, _) => Token::Var(
+                    name,
+                    vars.iter()
+                        .position(|v| *v == name)
+                        .expect("Every variable must be named"),
+                ),
+                t => t,
+            })
+            .collect();
+
+        Self::new(toks, vars.to_vec())
+    }
+
+    /// Walks the expression from the leaves up, handing each
+    /// token along with the results of its operands to `f`
+    pub(crate) fn fold<T>(&self, mut f: impl FnMut(Token<'a>, Vec<T>) -> T) -> T {
+        let mut stack: Vec<T> = Vec::new();
+        for t in self.toks.iter() {
+            let arity = t.arity();
+            if stack.len() < arity {
+                panic!("Ran {:?} without enough variables", t);
+            }
+            let args = stack.split_off(stack.len() - arity);
+            stack.push(f(*t, args));
+        }
+
+        if stack.len() != 1 {
+            panic!("The expression did not resove to a single value!");
+        }
+
+        stack.pop().unwrap()
     }
 }
@@ -0,0 +1,192 @@
+use super::{Token, Tokens};
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize, Serializer};
+
+/// [`Tokens`] that own the names of their variables
+///
+/// Unlike [`Tokens`], these can be deserialized from any input,
+/// like a reader, or a string where the names have escapes in them.
+///
+/// [`Tokens`]: `Tokens`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(try_from = "OwnedRepr"))]
+pub struct OwnedTokens {
+    /// the variables are named in `vars` rather than here
+    toks: Vec<Token<'static>>,
+    vars: Vec<String>,
+}
+
+impl OwnedTokens {
+    /// Borrow the expression as [`Tokens`]
+    ///
+    /// [`Tokens`]: `Tokens`
+    pub fn tokens(&self) -> Tokens<'_> {
+        let toks = self
+            .toks
+            .iter()
+            .map(|t| match *t {
+                Token::Var(_, v) => Token::Var(&self.vars[v], v),
+                t => t,
+            })
+            .collect();
+        Tokens::new(toks, self.vars.iter().map(String::as_str).collect())
+    }
+}
+
+impl<'a> From<&Tokens<'a>> for OwnedTokens {
+    fn from(tokens: &Tokens<'a>) -> Self {
+        let toks = tokens
+            .toks
+            .iter()
+            .map(|t| match *t {
+                Token::Var(_, v) => Token::Var("", v),
+                Token::Literal(b) => Token::Literal(b),
+                Token::Not => Token::Not,
+                Token::And => Token::And,
+                Token::Xor => Token::Xor,
+                Token::Or => Token::Or,
+                Token::Implication => Token::Implication,
+                Token::Equality => Token::Equality,
+                Token::LParen => Token::LParen,
+                Token::RParen => Token::RParen,
+            })
+            .collect();
+        Self {
+            toks,
+            vars: tokens.var_map.iter().map(|v| (*v).into()).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for OwnedTokens {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.tokens().serialize(serializer)
+    }
+}
+
+impl<'a> Tokens<'a> {
+    /// Check that every variable is named in `vars` at its index,
+    /// and that the tokens are a single expression in RPN, so
+    /// that deserialized tokens can be evaluated and printed
+    #[cfg(feature = "serde")]
+    fn check(&self) -> Result<(), &'static str> {
+        let mut depth = 0usize;
+        for t in &self.toks {
+            if let Token::Var(name, v) = *t {
+                match self.var_map.get(v) {
+                    Some(n) if *n == name => {}
+                    Some(_) => return Err("a variable is not named in `vars` at its index"),
+                    None => return Err("a variable index is past the end of `vars`"),
+                }
+            }
+            if let Token::LParen | Token::RParen = t {
+                return Err("the tokens are in RPN, which has no parenthesis");
+            }
+
+            depth = depth
+                .checked_sub(t.arity())
+                .ok_or("an operator is missing operands")?
+                + 1;
+        }
+
+        if depth == 1 {
+            Ok(())
+        } else {
+            Err("the tokens are not a single expression")
+        }
+    }
+}
+
+/// The serialized form of [`Tokens`], checked before it is used
+///
+/// [`Tokens`]: `Tokens`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+pub(super) struct TokensRepr<'a> {
+    #[serde(borrow)]
+    tokens: Vec<Token<'a>>,
+    #[serde(borrow)]
+    vars: Vec<&'a str>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> TryFrom<TokensRepr<'a>> for Tokens<'a> {
+    type Error = &'static str;
+
+    fn try_from(repr: TokensRepr<'a>) -> Result<Self, Self::Error> {
+        let tokens = Tokens::new(repr.tokens, repr.vars);
+        tokens.check()?;
+        Ok(tokens)
+    }
+}
+
+/// A [`Token`] that owns the name of its variable, serialized
+/// the same way, so that [`OwnedTokens`] read what [`Tokens`] write
+///
+/// [`Token`]: `Token`
+/// [`OwnedTokens`]: `OwnedTokens`
+/// [`Tokens`]: `Tokens`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "Token")]
+enum OwnedToken {
+    Literal(bool),
+    Var(String, usize),
+    Not,
+    And,
+    Xor,
+    Or,
+    Implication,
+    Equality,
+    LParen,
+    RParen,
+}
+
+/// The serialized form of [`OwnedTokens`], checked before it is used
+///
+/// [`OwnedTokens`]: `OwnedTokens`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct OwnedRepr {
+    tokens: Vec<OwnedToken>,
+    vars: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<OwnedRepr> for OwnedTokens {
+    type Error = &'static str;
+
+    fn try_from(repr: OwnedRepr) -> Result<Self, Self::Error> {
+        let vars = repr.vars;
+        let toks = repr
+            .tokens
+            .into_iter()
+            .map(|t| {
+                Ok(match t {
+                    OwnedToken::Var(name, v) => match vars.get(v) {
+                        Some(n) if *n == name => Token::Var("", v),
+                        Some(_) => return Err("a variable is not named in `vars` at its index"),
+                        None => return Err("a variable index is past the end of `vars`"),
+                    },
+                    OwnedToken::Literal(b) => Token::Literal(b),
+                    OwnedToken::Not => Token::Not,
+                    OwnedToken::And => Token::And,
+                    OwnedToken::Xor => Token::Xor,
+                    OwnedToken::Or => Token::Or,
+                    OwnedToken::Implication => Token::Implication,
+                    OwnedToken::Equality => Token::Equality,
+                    OwnedToken::LParen => Token::LParen,
+                    OwnedToken::RParen => Token::RParen,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let owned = Self { toks, vars };
+        owned.tokens().check()?;
+        Ok(owned)
+    }
+}
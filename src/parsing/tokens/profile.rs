@@ -0,0 +1,531 @@
+use super::{Token, TokenLiterals};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+/// The names of the tokens in a profile, in the
+/// order the lexer looks for their spellings
+const NAMES: [(Token<'static>, &str); 10] = [
+    (Token::Literal(false), "false"),
+    (Token::Literal(true), "true"),
+    (Token::Not, "not"),
+    (Token::And, "and"),
+    (Token::Xor, "xor"),
+    (Token::Or, "or"),
+    (Token::Implication, "implication"),
+    (Token::Equality, "equality"),
+    (Token::LParen, "left-paren"),
+    (Token::RParen, "right-paren"),
+];
+
+/// The profiles that ship with the library, and their spellings
+/// in the order of [`NAMES`]
+///
+/// [`NAMES`]: `NAMES`
+const PROFILES: [(&str, [&[&str]; 10]); 5] = [
+    (
+        "math-unicode",
+        [
+            &["⊥"],
+            &["⊤"],
+            &["¬"],
+            &["∧"],
+            &["⊕", "⊻"],
+            &["∨"],
+            &["→", "⇒"],
+            &["↔", "⇔", "≡"],
+            &["("],
+            &[")"],
+        ],
+    ),
+    (
+        "c-like",
+        [
+            &["false"],
+            &["true"],
+            &["!"],
+            &["&&"],
+            &["^"],
+            &["||"],
+            &["->"],
+            &["=="],
+            &["("],
+            &[")"],
+        ],
+    ),
+    (
+        "python",
+        [
+            &["False"],
+            &["True"],
+            &["not"],
+            &["and"],
+            &["^"],
+            &["or"],
+            &["->"],
+            &["=="],
+            &["("],
+            &[")"],
+        ],
+    ),
+    (
+        "verilog",
+        [
+            &["1'b0"],
+            &["1'b1"],
+            &["~", "!"],
+            &["&"],
+            &["^"],
+            &["|"],
+            &["->"],
+            &["=="],
+            &["("],
+            &[")"],
+        ],
+    ),
+    (
+        "spreadsheet",
+        [
+            &["FALSE"],
+            &["TRUE"],
+            &["NOT"],
+            &["AND"],
+            &["XOR"],
+            &["OR"],
+            &["->"],
+            &["="],
+            &["("],
+            &[")"],
+        ],
+    ),
+];
+
+/// Why a set of spellings can not be lexed as intended
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SpellingError {
+    /// A token without any spelling
+    Missing(Token<'static>),
+    /// An empty spelling, which would match everywhere
+    Empty(Token<'static>),
+    /// A spelling with whitespace in it, which
+    /// the lexer splits before it can match
+    Whitespace(Token<'static>, String),
+    /// A spelling used by two tokens
+    Duplicate {
+        /// The spelling
+        spelling: String,
+        /// The token that gets lexed
+        first: Token<'static>,
+        /// The token that never does
+        second: Token<'static>,
+    },
+    /// A spelling that is never lexed, as it starts
+    /// with a spelling that is looked for before it
+    Shadowed {
+        /// The spelling that is never lexed
+        spelling: String,
+        /// Its token
+        token: Token<'static>,
+        /// The spelling that gets lexed instead
+        by: String,
+        /// Its token
+        by_token: Token<'static>,
+    },
+}
+
+impl SpellingError {
+    /// The tokens involved
+    fn tokens(&self) -> (Token<'static>, Token<'static>) {
+        match *self {
+            SpellingError::Missing(t)
+            | SpellingError::Empty(t)
+            | SpellingError::Whitespace(t, _) => (t, t),
+            SpellingError::Duplicate { first, second, .. } => (first, second),
+            SpellingError::Shadowed {
+                token, by_token, ..
+            } => (token, by_token),
+        }
+    }
+}
+
+impl fmt::Display for SpellingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpellingError::Missing(t) => write!(f, "`{}` has no spellings", name(*t)),
+            SpellingError::Empty(t) => write!(f, "`{}` has an empty spelling", name(*t)),
+            SpellingError::Whitespace(t, s) => {
+                write!(f, "`{}` of `{}` has whitespace in it", s, name(*t))
+            }
+            SpellingError::Duplicate {
+                spelling,
+                first,
+                second,
+            } => write!(
+                f,
+                "`{}` spells both `{}` and `{}`",
+                spelling,
+                name(*first),
+                name(*second)
+            ),
+            SpellingError::Shadowed {
+                spelling,
+                token,
+                by,
+                by_token,
+            } => write!(
+                f,
+                "`{}` of `{}` is always lexed as `{}` of `{}`",
+                spelling,
+                name(*token),
+                by,
+                name(*by_token)
+            ),
+        }
+    }
+}
+
+/// What is wrong with a line of a profile
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ProfileErrorKind {
+    /// A line that is not `token = spelling, spelling`
+    MissingEquals,
+    /// A token name that is not one of the ten
+    UnknownToken(String),
+    /// The spellings can not be lexed as intended
+    Spelling(SpellingError),
+}
+
+/// An error found while reading a profile, along with
+/// the line, counting from 1, that it was found on
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ProfileError {
+    line: usize,
+    kind: ProfileErrorKind,
+}
+
+impl ProfileError {
+    /// The line the error was found on, counting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// What went wrong
+    pub fn kind(&self) -> &ProfileErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ProfileErrorKind::MissingEquals => f.write_str("expected `token = spelling, ...`"),
+            ProfileErrorKind::UnknownToken(t) => write!(f, "unknown token `{}`", t),
+            ProfileErrorKind::Spelling(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// The name of the token in a profile
+fn name(token: Token) -> &'static str {
+    NAMES
+        .iter()
+        .find(|(t, _)| *t == token)
+        .map(|(_, n)| *n)
+        .expect("Only spelled tokens have names")
+}
+
+/// Whether `s` starts with `prefix`, ignoring
+/// ASCII case like the lexer does
+fn starts_with(s: &str, prefix: &str) -> bool {
+    s.get(..prefix.len())
+        .map(|s| s.eq_ignore_ascii_case(prefix))
+        .unwrap_or(false)
+}
+
+impl TokenLiterals {
+    /// The names of the profiles that ship with
+    /// the library, see [`TokenLiterals::profile`]
+    ///
+    /// [`TokenLiterals::profile`]: `TokenLiterals::profile`
+    pub fn profile_names() -> impl Iterator<Item = &'static str> {
+        PROFILES.iter().map(|(name, _)| *name)
+    }
+
+    /// The built-in profile called `name`, one of
+    /// `math-unicode`, `c-like`, `python`, `verilog`
+    /// and `spreadsheet`
+    pub fn profile(name: &str) -> Option<Self> {
+        let (_, spellings) = PROFILES.iter().find(|(n, _)| *n == name)?;
+        let mut lit = Self::default();
+        for ((token, _), values) in NAMES.iter().zip(spellings.iter()) {
+            lit.tokens
+                .insert(*token, values.iter().map(|s| s.to_string()).collect());
+        }
+        Some(lit)
+    }
+
+    /// Read a profile, where every line is a token name
+    /// followed by `=` and its spellings separated by
+    /// commas, like `and = &&, and`. Lines starting
+    /// with `#` are comments.
+    ///
+    /// The token names are `true`, `false`, `not`, `and`,
+    /// `xor`, `or`, `implication`, `equality`, `left-paren`
+    /// and `right-paren`. Tokens the profile leaves out keep
+    /// their default spellings.
+    pub fn from_profile(src: &str) -> Result<Self, ProfileError> {
+        let mut lit = Self::default();
+        // the line each token was last spelled on
+        let mut lines: Vec<(Token, usize)> = Vec::new();
+
+        for (idx, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |kind| ProfileError {
+                line: idx + 1,
+                kind,
+            };
+
+            let eq = line
+                .find('=')
+                .ok_or_else(|| err(ProfileErrorKind::MissingEquals))?;
+            let token_name = line[..eq].trim();
+            let token = NAMES
+                .iter()
+                .find(|(_, n)| *n == token_name)
+                .map(|(t, _)| *t)
+                .ok_or_else(|| err(ProfileErrorKind::UnknownToken(token_name.into())))?;
+
+            let values = line[eq + 1..]
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(Into::into)
+                .collect();
+            lit.tokens.insert(token, values);
+            lines.retain(|(t, _)| *t != token);
+            lines.push((token, idx + 1));
+        }
+
+        lit.validate().map_err(|e| {
+            // the defaults are valid, so the profile
+            // spelled at least one of the tokens
+            let (a, b) = e.tokens();
+            let line = lines
+                .iter()
+                .filter(|(t, _)| *t == a || *t == b)
+                .map(|(_, line)| *line)
+                .max()
+                .unwrap_or(0);
+            ProfileError {
+                line,
+                kind: ProfileErrorKind::Spelling(e),
+            }
+        })?;
+        Ok(lit)
+    }
+
+    /// Write the spellings out as a profile, which
+    /// [`TokenLiterals::from_profile`] reads back
+    ///
+    /// [`TokenLiterals::from_profile`]: `TokenLiterals::from_profile`
+    pub fn to_profile(&self) -> String {
+        let mut out = String::new();
+        for (token, name) in NAMES.iter() {
+            out.push_str(name);
+            out.push_str(" = ");
+            out.push_str(&self.tokens[token].join(", "));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Add the spellings of `other` after those of `self`,
+    /// so that the expressions of both can be read. The
+    /// result may need to be [validated].
+    ///
+    /// [validated]: `TokenLiterals::validate`
+    pub fn merge(&mut self, other: &TokenLiterals) {
+        for (token, values) in &other.tokens {
+            let own = self.tokens.entry(*token).or_default();
+            for v in values {
+                if !own.iter().any(|o| o.eq_ignore_ascii_case(v)) {
+                    own.push(v.clone());
+                }
+            }
+        }
+    }
+
+    /// Check that every spelling of every token can be lexed
+    /// as that token, finding the first one that can not
+    pub fn validate(&self) -> Result<(), SpellingError> {
+        // every spelling, in the order the lexer tries them
+        let mut seen: Vec<(Token<'static>, &str)> = Vec::new();
+        for (token, values) in &self.tokens {
+            if values.is_empty() {
+                return Err(SpellingError::Missing(*token));
+            }
+            for v in values {
+                if v.is_empty() {
+                    return Err(SpellingError::Empty(*token));
+                }
+                if v.contains(char::is_whitespace) {
+                    return Err(SpellingError::Whitespace(*token, v.clone()));
+                }
+
+                for (by_token, by) in &seen {
+                    if by_token != token && by.eq_ignore_ascii_case(v) {
+                        return Err(SpellingError::Duplicate {
+                            spelling: v.clone(),
+                            first: *by_token,
+                            second: *token,
+                        });
+                    }
+                    if starts_with(v, by) && !by.eq_ignore_ascii_case(v) {
+                        return Err(SpellingError::Shadowed {
+                            spelling: v.clone(),
+                            token: *token,
+                            by: by.to_string(),
+                            by_token: *by_token,
+                        });
+                    }
+                }
+                seen.push((*token, v));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn profile(name: &str) -> TokenLiterals {
+        TokenLiterals::profile(name).expect(name)
+    }
+
+    /// The line and kind of the error reading `src` gives
+    fn error(src: &str) -> (usize, ProfileErrorKind) {
+        let err = TokenLiterals::from_profile(src).expect_err(src);
+        (err.line(), err.kind().clone())
+    }
+
+    #[test]
+    fn builtin_profiles_are_valid() {
+        let names: Vec<&str> = TokenLiterals::profile_names().collect();
+        assert_eq!(
+            names,
+            ["math-unicode", "c-like", "python", "verilog", "spreadsheet"]
+        );
+        for name in names {
+            assert_eq!(profile(name).validate(), Ok(()), "{}", name);
+        }
+        assert_eq!(TokenLiterals::default().validate(), Ok(()));
+        assert!(TokenLiterals::profile("c").is_none());
+    }
+
+    #[test]
+    fn profiles_round_trip() {
+        let names = TokenLiterals::profile_names();
+        for lit in names.map(profile).chain(Some(TokenLiterals::default())) {
+            let src = lit.to_profile();
+            assert_eq!(TokenLiterals::from_profile(&src), Ok(lit), "{}", src);
+        }
+        assert_eq!(
+            profile("python").to_profile().lines().next(),
+            Some("false = False")
+        );
+    }
+
+    #[test]
+    fn reads_profiles() {
+        let lit = TokenLiterals::from_profile("# words\n\n  and = AND , & \nor=OR").unwrap();
+        assert_eq!(lit.and(), ["AND", "&"]);
+        assert_eq!(lit.or(), ["OR"]);
+        // the tokens left out keep their defaults
+        assert_eq!(lit.not(), TokenLiterals::default().not());
+    }
+
+    #[test]
+    fn merged_spellings_come_after_their_own() {
+        let mut lit = profile("c-like");
+        lit.merge(&profile("python"));
+        assert_eq!(lit.and(), ["&&", "and"]);
+        assert_eq!(lit.not(), ["!", "not"]);
+        // spellings are the same whatever their case
+        assert_eq!(lit.lit_true(), ["true"]);
+        assert_eq!(lit.validate(), Ok(()));
+
+        let mut lit = profile("math-unicode");
+        lit.merge(&profile("spreadsheet"));
+        assert_eq!(lit.equality(), ["↔", "⇔", "≡", "="]);
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        assert_eq!(error("# and\nand &&"), (2, ProfileErrorKind::MissingEquals));
+        assert_eq!(
+            error("and = &&\nnand = !&"),
+            (2, ProfileErrorKind::UnknownToken("nand".into()))
+        );
+        assert_eq!(
+            error("and = ,"),
+            (
+                1,
+                ProfileErrorKind::Spelling(SpellingError::Missing(Token::And))
+            )
+        );
+        assert_eq!(
+            error("or = a b"),
+            (
+                1,
+                ProfileErrorKind::Spelling(SpellingError::Whitespace(Token::Or, "a b".into()))
+            )
+        );
+    }
+
+    #[test]
+    fn reports_conflicts_on_the_later_line() {
+        let dup = SpellingError::Duplicate {
+            spelling: "&".into(),
+            first: Token::And,
+            second: Token::Or,
+        };
+        assert_eq!(
+            error("or = &\n# both\nand = &"),
+            (3, ProfileErrorKind::Spelling(dup.clone()))
+        );
+        assert_eq!(
+            TokenLiterals::from_profile("and = &\nor = &")
+                .unwrap_err()
+                .to_string(),
+            "line 2: `&` spells both `and` and `or`"
+        );
+
+        let shadowed = SpellingError::Shadowed {
+            spelling: "&|".into(),
+            token: Token::Or,
+            by: "&".into(),
+            by_token: Token::And,
+        };
+        assert_eq!(
+            error("xor = x\nor = &|\nand = &\nnot = !"),
+            (3, ProfileErrorKind::Spelling(shadowed))
+        );
+
+        let mut lit = profile("verilog");
+        lit.set_xor(vec!["~^".into()]);
+        assert_eq!(
+            lit.validate().unwrap_err().to_string(),
+            "`~^` of `xor` is always lexed as `~` of `not`"
+        );
+        assert_eq!(dup.to_string(), "`&` spells both `and` and `or`");
+    }
+}
@@ -1,6 +1,10 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// All the possible supported tokens in a
 /// Boolean Expression
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Token<'a> {
     //
     // Values
@@ -65,4 +69,16 @@ impl<'a> Token<'a> {
             _ => usize::max_value(),
         }
     }
+
+    /// The amount of operands the [`Token`] takes,
+    /// `0` for values and parenthesis
+    ///
+    /// [`Token`]: `Token`
+    pub fn arity(&self) -> usize {
+        match self {
+            Self::Not => 1,
+            Self::And | Self::Xor | Self::Or | Self::Implication | Self::Equality => 2,
+            _ => 0,
+        }
+    }
 }
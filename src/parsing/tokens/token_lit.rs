@@ -1,9 +1,20 @@
+#[cfg(feature = "serde")]
+use super::SpellingError;
 use super::Token;
 use alloc::{collections::btree_map::BTreeMap, string::String, vec, vec::Vec};
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "Spellings", into = "Spellings")
+)]
 pub struct TokenLiterals {
-    tokens: BTreeMap<Token<'static>, Vec<String>>,
+    pub(super) tokens: BTreeMap<Token<'static>, Vec<String>>,
 }
 
 impl TokenLiterals {
@@ -115,12 +126,11 @@ impl TokenLiterals {
         fn find(input: &str, tokens: &[String]) -> Option<usize> {
             tokens
                 .iter()
-                .find(|t| {
-                    if input.len() < t.len() {
-                        false
-                    } else {
-                        t.eq_ignore_ascii_case(&input[..t.len()])
-                    }
+                // the input may be cut inside of a character,
+                // where it can not start with the token
+                .find(|t| match input.get(..t.len()) {
+                    Some(start) => t.eq_ignore_ascii_case(start),
+                    None => false,
                 })
                 .map(|v| v.len())
         }
@@ -196,3 +206,74 @@ impl Default for TokenLiterals {
         Self { tokens: map }
     }
 }
+
+/// The serialized form of [`TokenLiterals`], with a field per
+/// token so that it can be written out by any format, even
+/// those whose maps only have string keys
+///
+/// [`TokenLiterals`]: `TokenLiterals`
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct Spellings {
+    lit_true: Vec<String>,
+    lit_false: Vec<String>,
+    not: Vec<String>,
+    and: Vec<String>,
+    xor: Vec<String>,
+    or: Vec<String>,
+    implication: Vec<String>,
+    equality: Vec<String>,
+    left_paren: Vec<String>,
+    right_paren: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl Default for Spellings {
+    fn default() -> Self {
+        TokenLiterals::default().into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TokenLiterals> for Spellings {
+    fn from(lit: TokenLiterals) -> Self {
+        Self {
+            lit_true: lit.lit_true().to_vec(),
+            lit_false: lit.lit_false().to_vec(),
+            not: lit.not().to_vec(),
+            and: lit.and().to_vec(),
+            xor: lit.xor().to_vec(),
+            or: lit.or().to_vec(),
+            implication: lit.implication().to_vec(),
+            equality: lit.equality().to_vec(),
+            left_paren: lit.left_paren().to_vec(),
+            right_paren: lit.right_paren().to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Spellings> for TokenLiterals {
+    type Error = SpellingError;
+
+    /// Read the spellings back, as long as they can be
+    /// lexed, as per [`TokenLiterals::validate`]
+    ///
+    /// [`TokenLiterals::validate`]: `TokenLiterals::validate`
+    fn try_from(s: Spellings) -> Result<Self, Self::Error> {
+        let mut lit = TokenLiterals::default();
+        lit.set_lit_true(s.lit_true);
+        lit.set_lit_false(s.lit_false);
+        lit.set_not(s.not);
+        lit.set_and(s.and);
+        lit.set_xor(s.xor);
+        lit.set_or(s.or);
+        lit.set_implication(s.implication);
+        lit.set_equality(s.equality);
+        lit.set_left_paren(s.left_paren);
+        lit.set_right_paren(s.right_paren);
+        lit.validate()?;
+        Ok(lit)
+    }
+}
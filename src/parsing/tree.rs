@@ -0,0 +1,97 @@
+use super::{Token, Tokens};
+use alloc::{boxed::Box, vec::Vec};
+
+/// A Boolean Expression as a syntax tree, rebuilt
+/// from the Reverse Polish Notation of [`Tokens`]
+///
+/// [`Tokens`]: `Tokens`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Node<'a> {
+    /// A literal value
+    Lit(bool),
+    /// A variable, with its name and index
+    Var(&'a str, usize),
+    /// The NOT of a sub expression
+    Not(Box<Node<'a>>),
+    /// A binary operator applied to two sub expressions
+    Op(Token<'a>, Box<Node<'a>>, Box<Node<'a>>),
+}
+
+impl<'a> Node<'a> {
+    pub(crate) fn from_tokens(toks: &Tokens<'a>) -> Self {
+        toks.fold(|t, mut args| match t {
+            Token::Literal(v) => Node::Lit(v),
+            Token::Var(name, idx) => Node::Var(name, idx),
+            Token::Not => Node::Not(Box::new(args.pop().expect("Not has one operand"))),
+            t => {
+                let r = args.pop().expect("Binary operators have two operands");
+                let l = args.pop().expect("Binary operators have two operands");
+                Node::Op(t, Box::new(l), Box::new(r))
+            }
+        })
+    }
+
+    /// Turn the tree back into [`Tokens`], over the given variables
+    ///
+    /// [`Tokens`]: `Tokens`
+    pub(crate) fn to_tokens(&self, var_map: Vec<&'a str>) -> Tokens<'a> {
+        let mut toks = Vec::new();
+        self.push_tokens(&mut toks);
+        Tokens::new(toks, var_map)
+    }
+
+    fn push_tokens(&self, toks: &mut Vec<Token<'a>>) {
+        match self {
+            Node::Lit(v) => toks.push(Token::Literal(*v)),
+            Node::Var(name, idx) => toks.push(Token::Var(name, *idx)),
+            Node::Not(v) => {
+                v.push_tokens(toks);
+                toks.push(Token::Not);
+            }
+            Node::Op(t, l, r) => {
+                l.push_tokens(toks);
+                r.push_tokens(toks);
+                toks.push(*t);
+            }
+        }
+    }
+
+    pub(crate) fn not(v: Node<'a>) -> Self {
+        Node::Not(Box::new(v))
+    }
+
+    pub(crate) fn op(t: Token<'a>, l: Node<'a>, r: Node<'a>) -> Self {
+        Node::Op(t, Box::new(l), Box::new(r))
+    }
+
+    /// Collect the operands of a chain of the same operator,
+    /// so that `a & (b & c)` gives `[a, b, c]`
+    pub(crate) fn chain(&self, op: Token<'a>) -> Vec<&Node<'a>> {
+        let mut terms = Vec::new();
+        self.push_chain(op, &mut terms);
+        terms
+    }
+
+    fn push_chain<'s>(&'s self, op: Token<'a>, terms: &mut Vec<&'s Node<'a>>) {
+        match self {
+            Node::Op(t, l, r) if *t == op => {
+                l.push_chain(op, terms);
+                r.push_chain(op, terms);
+            }
+            n => terms.push(n),
+        }
+    }
+
+    /// Join the terms with the given operator, associating to the
+    /// right. An empty chain is the identity of the operator.
+    pub(crate) fn from_chain(op: Token<'a>, mut terms: Vec<Node<'a>>) -> Self {
+        let mut acc = match terms.pop() {
+            Some(v) => v,
+            None => return Node::Lit(op == Token::And || op == Token::Equality),
+        };
+        while let Some(t) = terms.pop() {
+            acc = Node::op(op, t, acc);
+        }
+        acc
+    }
+}
@@ -0,0 +1,353 @@
+//! The interactive shell of the `truth-tester` tool
+
+use crate::{assignment, caret, set_spellings, spellings, table, unknown_token, Options, TOKENS};
+use std::io::{self, BufRead, Write};
+use truth_tester::{
+    eval::{split_word, Tester},
+    parsing::{ParseError, Parser, TokenLiterals, Tokens},
+};
+
+const PROMPT: &str = "> ";
+
+const HELP: &str = "\
+<expr>                  Show the truth table of the expression,
+                        which is then called `it`
+:let <name> <expr>      Keep the expression under the name
+:list                   Show every named expression
+:table [f]              Show the truth table of `f`
+:sat [f]                Show an assignment that makes `f` true
+:taut [f]               Tell whether `f` is a tautology
+:equiv <f> <g>          Show an assignment where `f` and `g` differ
+:equiv <f> ; <g>        The same, for expressions with spaces in them
+:vars [f]               Show the variables of `f` that are not fixed
+:literals               Show the spellings of every token
+:literals <token> <spelling>...
+                        Spell the token only in these ways
+:literals default       Go back to the default spellings
+:literals <profile>     Use one of the built-in profiles
+:fix <var>=<0|1>...     Keep the variables at these values
+:unfix                  Let every variable vary again
+:help                   Show this message
+:quit                   Leave
+
+Without `f` the command works on `it`, and when `f` is not the
+name of an expression it is read as an expression itself.";
+
+/// The named expressions and settings of a shell
+struct Session<'o> {
+    opts: &'o Options,
+    literals: TokenLiterals,
+    /// the names and sources of the expressions,
+    /// which are parsed again whenever they are used
+    defs: Vec<(String, String)>,
+    /// the variables kept at a value
+    fixed: Vec<(String, bool)>,
+}
+
+/// Read commands from stdin until it ends or `:quit`
+pub fn run(opts: &Options) -> Result<i32, String> {
+    let mut session = Session::new(opts);
+
+    let stdin = io::stdin();
+    let mut line = String::new();
+    loop {
+        print!("{}", PROMPT);
+        io::stdout().flush().map_err(|e| e.to_string())?;
+
+        line.clear();
+        if stdin
+            .lock()
+            .read_line(&mut line)
+            .map_err(|e| e.to_string())?
+            == 0
+        {
+            println!();
+            return Ok(0);
+        }
+
+        match session.command(line.trim_end()) {
+            Ok(true) => (),
+            Ok(false) => return Ok(0),
+            Err(msg) => println!("{}", msg),
+        }
+    }
+}
+
+impl<'o> Session<'o> {
+    fn new(opts: &'o Options) -> Self {
+        Self {
+            opts,
+            literals: opts.literals.clone(),
+            defs: Vec::new(),
+            fixed: Vec::new(),
+        }
+    }
+
+    /// Run a single line, returning whether to keep going
+    fn command(&mut self, line: &str) -> Result<bool, String> {
+        let input = line.trim_start();
+        if input.is_empty() {
+            return Ok(true);
+        }
+        if !input.starts_with(':') {
+            let tester = self.expr(line, input)?;
+            print!("{}", table(self.opts, &tester));
+            // the source rather than the name, so that `it`
+            // stays the same if the name is defined again
+            let src = self.source(input).to_string();
+            self.define("it", &src);
+            return Ok(true);
+        }
+
+        let (cmd, args) = split_word(input);
+        match cmd {
+            ":quit" | ":q" => return Ok(false),
+            ":help" | ":h" => println!("{}", HELP),
+            ":let" => {
+                let (name, src) = split_word(args);
+                if name.is_empty() || src.is_empty() {
+                    return Err("`:let` takes a name and an expression".into());
+                }
+                self.expr(line, src)?;
+                self.define(name, src);
+            }
+            ":list" => {
+                for (name, src) in &self.defs {
+                    println!("{} := {}", name, src);
+                }
+            }
+            ":table" => print!("{}", table(self.opts, &self.expr(line, args)?)),
+            ":sat" => {
+                let tester = self.expr(line, args)?;
+                let success = tester.successes().next();
+                match success {
+                    Some(state) => {
+                        let vals = tester.var_vals(state);
+                        println!("{}", assignment(self.opts, tester.vars(), vals));
+                    }
+                    None => println!("unsatisfiable"),
+                }
+            }
+            ":taut" => {
+                let tester = self.expr(line, args)?;
+                let failure = tester.failures().next();
+                match failure {
+                    Some(state) => {
+                        let vals = tester.var_vals(state);
+                        println!("fails for {}", assignment(self.opts, tester.vars(), vals));
+                    }
+                    None => println!("tautology"),
+                }
+            }
+            ":equiv" => {
+                // without a `;` the first word is `f`, and the rest
+                // must be a single word too
+                let (f, g) = match args.find(';') {
+                    Some(idx) => (args[..idx].trim(), args[idx + 1..].trim()),
+                    None => split_word(args),
+                };
+                if f.is_empty()
+                    || g.is_empty()
+                    || (!args.contains(';') && g.contains(char::is_whitespace))
+                {
+                    return Err("`:equiv` takes two words, or two expressions split by `;`".into());
+                }
+                match self.expr(line, f)?.counterexample(&self.expr(line, g)?) {
+                    None => println!("equivalent"),
+                    Some(cex) => {
+                        let (names, vals): (Vec<&str>, Vec<bool>) =
+                            cex.assignment().iter().copied().unzip();
+                        let values = self.opts.values;
+                        println!(
+                            "{} gives {} and {}",
+                            assignment(self.opts, &names, vals.into_iter()),
+                            values.spell(cex.left()),
+                            values.spell(cex.right())
+                        );
+                    }
+                }
+            }
+            ":vars" => println!("{}", self.expr(line, args)?.vars().join(" ")),
+            ":literals" => self.literals(args)?,
+            ":fix" => self.fix(args)?,
+            ":unfix" => self.fixed.clear(),
+            _ => return Err(format!("unknown command `{}`, see `:help`", cmd)),
+        }
+        Ok(true)
+    }
+
+    /// Keep `src` under `name`, replacing what was there
+    fn define(&mut self, name: &str, src: &str) {
+        match self.defs.iter_mut().find(|(n, _)| n == name) {
+            Some((_, old)) => *old = src.into(),
+            None => self.defs.push((name.into(), src.into())),
+        }
+    }
+
+    /// The expression called `arg`, or `it` when there is no
+    /// `arg`, or else `arg` read as an expression. It must be
+    /// a slice of `line` so that errors point at their place.
+    fn expr<'s>(&'s self, line: &'s str, arg: &'s str) -> Result<Tester<Tokens<'s>>, String> {
+        if let Some((name, src)) = self.def(arg) {
+            // the spellings may have changed since it was
+            // defined, so show it again along with the error
+            return self.parse(src).map_err(|e| {
+                let def = format!("{} := ", name);
+                format!("{}{}\n{}", def, src, caret(src, def.chars().count(), &e))
+            });
+        }
+        if arg.is_empty() {
+            return Err("there is no expression yet".into());
+        }
+
+        self.parse(arg).map_err(|e| {
+            let before = arg.as_ptr() as usize - line.as_ptr() as usize;
+            caret(arg, PROMPT.len() + line[..before].chars().count(), &e)
+        })
+    }
+
+    /// The name and source of the expression called `arg`,
+    /// or `it` when there is no `arg`
+    fn def(&self, arg: &str) -> Option<&(String, String)> {
+        let name = if arg.is_empty() { "it" } else { arg };
+        self.defs.iter().find(|(n, _)| n == name)
+    }
+
+    /// The source [`expr`] reads for `arg`
+    ///
+    /// [`expr`]: `Session::expr`
+    fn source<'s>(&'s self, arg: &'s str) -> &'s str {
+        self.def(arg).map_or(arg, |(_, src)| src)
+    }
+
+    /// Parse `src` with the fixed variables replaced by their values
+    fn parse<'s>(&self, src: &'s str) -> Result<Tester<Tokens<'s>>, ParseError> {
+        let mut expr =
+            Parser::parse_with_literals(src, self.literals.clone()).try_shunting_yard()?;
+        for (name, val) in &self.fixed {
+            if let Some(var) = expr.vars().iter().position(|v| v == name) {
+                expr = expr.assign(var, *val);
+            }
+        }
+        Ok(Tester::with_tokens(expr))
+    }
+
+    /// Show or change the spellings of the tokens
+    fn literals(&mut self, args: &str) -> Result<(), String> {
+        let (token, rest) = split_word(args);
+        match token {
+            "" => {
+                for token in TOKENS.iter() {
+                    let spelled = spellings(&self.literals, token).unwrap_or_default();
+                    println!("{}: {}", token, spelled.join(" "));
+                }
+            }
+            "default" if rest.is_empty() => self.literals = TokenLiterals::default(),
+            _ if rest.is_empty() => match TokenLiterals::profile(token) {
+                Some(profile) => self.literals = profile,
+                None => return Err(format!("`{}` is not a profile, or needs spellings", token)),
+            },
+            _ => {
+                let mut literals = self.literals.clone();
+                let values = rest.split_whitespace().map(Into::into).collect();
+                if !set_spellings(&mut literals, token, values) {
+                    return Err(unknown_token(token));
+                }
+                literals
+                    .validate()
+                    .map_err(|e| format!("the spellings can not be used, {}", e))?;
+                self.literals = literals;
+            }
+        }
+        Ok(())
+    }
+
+    /// Show or add to the fixed variables
+    fn fix(&mut self, args: &str) -> Result<(), String> {
+        if args.is_empty() {
+            if self.fixed.is_empty() {
+                println!("nothing is fixed");
+            }
+            for (name, val) in &self.fixed {
+                println!("{}={}", name, self.opts.values.spell(*val));
+            }
+            return Ok(());
+        }
+
+        // read every pair before fixing any of them
+        let mut pairs = Vec::new();
+        for pair in args.split_whitespace() {
+            let (name, val) = match pair.find('=') {
+                Some(idx) => (&pair[..idx], &pair[idx + 1..]),
+                None => return Err(format!("expected `<var>=<value>`, found `{}`", pair)),
+            };
+            let val = match val {
+                "1" | "true" | "T" => true,
+                "0" | "false" | "F" => false,
+                _ => return Err(format!("`{}` is not a value", val)),
+            };
+            pairs.push((name, val));
+        }
+        for (name, val) in pairs {
+            match self.fixed.iter_mut().find(|(n, _)| n == name) {
+                Some((_, old)) => *old = val,
+                None => self.fixed.push((name.into(), val)),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Session;
+    use crate::parse_args;
+
+    #[test]
+    fn it_is_the_source_of_a_named_expression() {
+        let opts = parse_args(vec!["repl".into()]).unwrap();
+        let mut session = Session::new(&opts);
+        for line in [":let f a & b", "f", ":table it", ":let f a | b"].iter() {
+            assert_eq!(session.command(line), Ok(true), "{}", line);
+        }
+
+        assert_eq!(session.source("it"), "a & b");
+        let it = session.expr("it", "it").unwrap().truth_table();
+        let and = session.expr("a & b", "a & b").unwrap().truth_table();
+        assert_eq!(it, and);
+    }
+
+    #[test]
+    fn it_is_the_last_expression() {
+        let opts = parse_args(vec!["repl".into()]).unwrap();
+        let mut session = Session::new(&opts);
+        assert!(session.command(":table").is_err());
+        assert_eq!(session.command("a -> b"), Ok(true));
+        assert_eq!(session.source(""), "a -> b");
+        assert_eq!(session.command("it"), Ok(true));
+        assert_eq!(session.source(""), "a -> b");
+    }
+
+    #[test]
+    fn equiv_splits_expressions_at_a_semicolon() {
+        let opts = parse_args(vec!["repl".into()]).unwrap();
+        let mut session = Session::new(&opts);
+        assert_eq!(session.command(":let f a -> b"), Ok(true));
+        for line in [":equiv f !a|b", ":equiv f ; !a | b", ":equiv a & b;b & a"].iter() {
+            assert_eq!(session.command(line), Ok(true), "{}", line);
+        }
+
+        let err = Err("`:equiv` takes two words, or two expressions split by `;`".into());
+        for line in [
+            ":equiv f",
+            ":equiv f !a | b",
+            ":equiv a & b ;",
+            ":equiv ; f",
+        ]
+        .iter()
+        {
+            assert_eq!(session.command(line), err, "{}", line);
+        }
+        assert!(session.command(":equiv f ; a &").is_err());
+    }
+}
@@ -0,0 +1,55 @@
+//! Checks the assertion macros, and that their messages
+//! show the first assignment an expression fails for
+#![cfg(all(feature = "tester", feature = "parsing"))]
+
+use truth_tester::{assert_contradiction, assert_equivalent, assert_satisfiable, assert_tautology};
+
+#[test]
+fn passes_on_what_holds() {
+    assert_tautology!("a | !a");
+    assert_tautology!("(a -> b) <=> (!a | b)", "material implication");
+    assert_contradiction!("a & !a");
+    assert_satisfiable!("a & !b");
+    assert_equivalent!("a & b", "b & a");
+    assert_equivalent!("!(a | b)", "!b & !a",);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `a | b` is not a tautology
+ assignment: a=0 b=0
+     result: 0")]
+fn tautology_shows_the_first_failure() {
+    assert_tautology!("a | b");
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `a & !b` is not a contradiction: case 3
+ assignment: a=1 b=0
+     result: 1")]
+fn contradiction_shows_the_first_success_and_message() {
+    assert_contradiction!("a & !b", "case {}", 3);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `b & !b` is not satisfiable
+ assignment: b=0
+     result: 0")]
+fn satisfiable_shows_an_assignment() {
+    assert_satisfiable!("b & !b");
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `a -> b` is not equivalent to `b -> a`
+ assignment: a=1 b=0
+     result: 0 and 1")]
+fn equivalent_matches_reordered_variables() {
+    assert_equivalent!("a -> b", "b -> a");
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `a & b` is not equivalent to `c | b`
+ assignment: a=0 b=1 c=0
+     result: 0 and 1")]
+fn equivalent_joins_disjoint_variables() {
+    assert_equivalent!("a & b", "c | b");
+}
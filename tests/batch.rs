@@ -0,0 +1,123 @@
+//! Checks batch verification, and its reports, against a sample
+#![cfg(all(feature = "tester", feature = "parsing"))]
+
+use truth_tester::eval::{Batch, BatchErrorKind};
+
+/// Records that pass and fail each expectation, with a
+/// name that has to be escaped in both reports
+const SAMPLE: &str = "\
+# a sample
+excluded   tautology            a | !a
+both       contradiction        a & !b
+safe       satisfiable          a & !a
+safe_too   equivalent demorgan  !(a & b)
+demorgan   satisfiable          !a | !b
+\"q<\"      equivalent both      a ^ b
+";
+
+const JSON: &str = r#"{
+  "passed": 3,
+  "failed": 3,
+  "records": [
+    {"name": "excluded", "line": 2, "expectation": "tautology", "passed": true},
+    {"name": "both", "line": 3, "expectation": "contradiction", "passed": false, "counterexample": {"a": true, "b": false}},
+    {"name": "safe", "line": 4, "expectation": "satisfiable", "passed": false, "counterexample": null},
+    {"name": "safe_too", "line": 5, "expectation": "equivalent", "other": "demorgan", "passed": true},
+    {"name": "demorgan", "line": 6, "expectation": "satisfiable", "passed": true},
+    {"name": "\"q<\"", "line": 7, "expectation": "equivalent", "other": "both", "passed": false, "left": true, "right": false, "counterexample": {"a": false, "b": true}}
+  ]
+}
+"#;
+
+const JUNIT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="sample &amp; co" tests="6" failures="3" errors="0">
+  <testcase name="excluded" classname="sample &amp; co"/>
+  <testcase name="both" classname="sample &amp; co">
+    <failure type="contradiction" message="`both` is not a contradiction, it is true for a=1 b=0"/>
+  </testcase>
+  <testcase name="safe" classname="sample &amp; co">
+    <failure type="satisfiable" message="`safe` is not satisfiable"/>
+  </testcase>
+  <testcase name="safe_too" classname="sample &amp; co"/>
+  <testcase name="demorgan" classname="sample &amp; co"/>
+  <testcase name="&quot;q&lt;&quot;" classname="sample &amp; co">
+    <failure type="equivalent" message="`&quot;q&lt;&quot;` is not equivalent to `both`, they give 1 and 0 for a=0 b=1"/>
+  </testcase>
+</testsuite>
+"#;
+
+#[test]
+fn verifies_sample() {
+    let report = Batch::parse(SAMPLE).unwrap().verify();
+    let passed: Vec<bool> = report.verdicts().iter().map(|v| v.passed()).collect();
+    assert_eq!(passed, [true, false, false, true, true, false]);
+    assert_eq!((report.passed(), report.failed()), (3, 3));
+    assert!(!report.is_success());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn verifies_sample_in_parallel() {
+    let batch = Batch::parse(SAMPLE).unwrap();
+    assert_eq!(batch.verify_par(), batch.verify());
+}
+
+#[test]
+fn writes_json_report() {
+    let mut out = String::new();
+    let report = Batch::parse(SAMPLE).unwrap().verify();
+    report.write_json(&mut out).unwrap();
+    assert_eq!(out, JSON);
+}
+
+#[test]
+fn writes_junit_report() {
+    let mut out = String::new();
+    let report = Batch::parse(SAMPLE).unwrap().verify();
+    report.write_junit(&mut out, "sample & co").unwrap();
+    assert_eq!(out, JUNIT);
+}
+
+#[test]
+fn writes_empty_reports() {
+    let report = Batch::parse("# nothing\n").unwrap().verify();
+    assert!(report.is_success());
+
+    let mut out = String::new();
+    report.write_json(&mut out).unwrap();
+    assert_eq!(
+        out,
+        "{\n  \"passed\": 0,\n  \"failed\": 0,\n  \"records\": []\n}\n"
+    );
+}
+
+#[test]
+fn reports_malformed_batches() {
+    let cases = [
+        ("a", BatchErrorKind::MissingExpectation),
+        (
+            "a provable b",
+            BatchErrorKind::UnknownExpectation("provable".into()),
+        ),
+        ("a equivalent", BatchErrorKind::MissingOther),
+        ("a tautology", BatchErrorKind::MissingExpression),
+        ("a equivalent b c", BatchErrorKind::UnknownName("b".into())),
+    ];
+    for (src, kind) in cases.iter() {
+        let err = Batch::parse(src).map(|_| ()).unwrap_err();
+        assert_eq!((err.line(), err.kind()), (1, kind), "{:?}", src);
+    }
+
+    let err = Batch::parse("a tautology b\n\na satisfiable b")
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(err.line(), 3);
+    assert_eq!(err.kind(), &BatchErrorKind::DuplicateName("a".into()));
+    assert_eq!(err.to_string(), "line 3: `a` was already used");
+
+    let err = Batch::parse("a tautology b &").map(|_| ()).unwrap_err();
+    match err.kind() {
+        BatchErrorKind::Expression(e) => assert_eq!(e.offset(), 3),
+        kind => panic!("expected a parse error, found {:?}", kind),
+    }
+}
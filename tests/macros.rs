@@ -0,0 +1,60 @@
+//! Checks that `bool_expr!` expands to the same expressions
+//! as the runtime parser, and reports malformed ones where
+//! they go wrong
+#![cfg(all(feature = "macros", feature = "parsing"))]
+
+use truth_tester::{
+    bool_expr,
+    eval::{Compiled, Tester},
+};
+
+const IMPLIES: Compiled<2> = bool_expr!(a -> !b);
+const VARS: [&str; 2] = IMPLIES.vars();
+
+/// Check that `expr` has the variables and truth table
+/// of `src` read by the runtime parser
+fn assert_expands<const N: usize>(expr: Compiled<N>, src: &str) {
+    let parsed = Tester::parse(src);
+    let compiled = Tester::with_compiled(expr);
+    assert_eq!(compiled.vars(), parsed.vars(), "{:?}", src);
+    assert_eq!(compiled.truth_table(), parsed.truth_table(), "{:?}", src);
+}
+
+#[test]
+fn exposes_vars_at_compile_time() {
+    assert_eq!(VARS, ["a", "b"]);
+    assert_eq!(IMPLIES.var_count(), 2);
+    assert_eq!(bool_expr!(true | false).vars(), [""; 0]);
+}
+
+#[test]
+fn expands_like_the_parser() {
+    assert_expands(IMPLIES, "a -> !b");
+    assert_expands(bool_expr!(a & b | c), "a & b | c");
+    assert_expands(bool_expr!(a | b & c), "a | b & c");
+    assert_expands(bool_expr!(a ^ b ^ c), "a ^ b ^ c");
+    assert_expands(bool_expr!(a -> b -> c), "a -> b -> c");
+    assert_expands(bool_expr!(a = b -> c), "a = b -> c");
+    assert_expands(
+        bool_expr!(!(a && b) <=> (!a || !b)),
+        "!(a && b) <=> (!a || !b)",
+    );
+    assert_expands(bool_expr!(a * [b + {c}] == ~a), "a * [b + {c}] == ~a");
+    assert_expands(
+        bool_expr!(not x and y xor z or TRUE),
+        "not x and y xor z or TRUE",
+    );
+    assert_expands(bool_expr!(b & a & b), "b & a & b");
+}
+
+#[test]
+fn reads_raw_identifiers_as_variables() {
+    let expr = bool_expr!(r#and & !r#not);
+    assert_eq!(expr.vars(), ["and", "not"]);
+}
+
+#[test]
+fn reports_malformed_expressions() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,385 @@
+//! Checks the lexer, parser, evaluator and printer against a
+//! reference, which parses the default ASCII spellings by
+//! recursive descent and evaluates the tree it builds.
+//!
+//! The intended semantics are that `!` binds tightest, then `&`,
+//! `^`, `|`, `->` and `=`, with operators of the same precedence
+//! grouping to the right, so `a -> b -> c` is `a -> (b -> c)`.
+#![cfg(all(feature = "tester", feature = "parsing"))]
+
+use truth_tester::{
+    eval::{Expression, Tester},
+    parsing::{Generator, SplitMix64, Token, TokenLiterals, Tokens},
+};
+
+/// Expressions that cover every spelling, the precedence of
+/// every pair of operators and chains of each operator
+const CORPUS: &[&str] = &[
+    "true",
+    "false",
+    "a",
+    "!a",
+    "!!a",
+    "~a",
+    "not a",
+    "a & b",
+    "a && b",
+    "a * b",
+    "a and b",
+    "a AND b",
+    "a ^ b",
+    "a xor b",
+    "a | b",
+    "a || b",
+    "a + b",
+    "a or b",
+    "a -> b",
+    "a => b",
+    "a = b",
+    "a == b",
+    "a <=> b",
+    "TRUE & False",
+    "false -> a",
+    "!a & b",
+    "!(a & b)",
+    "a & !!b",
+    "a | b & c",
+    "a & b | c",
+    "a ^ b & c",
+    "a & b ^ c",
+    "a | b ^ c",
+    "a ^ b | c",
+    "a -> b | c",
+    "a | b -> c",
+    "a = b -> c",
+    "a -> b = c",
+    "a & b & c",
+    "a ^ b ^ c",
+    "a | b | c",
+    "a -> b -> c",
+    "(a -> b) -> c",
+    "a -> (b -> c)",
+    "a = b = c",
+    "(a = b) = c",
+    "a -> b -> c -> d",
+    "((a -> b) -> c) -> d",
+    "a = b -> c = d",
+    "(a = b) -> c",
+    "[a | b] & {c}",
+    "{[(a)]}",
+    "!a->b",
+    "a|b&c^d->e=f",
+    "x1 -> y2 -> z3",
+    "a | b -> c & d = e ^ f",
+    "!(a -> b) = (a & !b)",
+    "(a ^ b) ^ (b ^ c) -> a = c",
+];
+
+/// A binary operator, from the loosest binding
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Equality,
+    Implication,
+    Or,
+    Xor,
+    And,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Lexeme {
+    Lit(bool),
+    Var(String),
+    Not,
+    Op(Op),
+    Open,
+    Close,
+}
+
+#[derive(Debug)]
+enum Ref {
+    Lit(bool),
+    Var(String),
+    Not(Box<Ref>),
+    Bin(Op, Box<Ref>, Box<Ref>),
+}
+
+/// The spellings made of symbols, the longest first
+const SYMBOLS: &[(&str, Lexeme)] = &[
+    ("<=>", Lexeme::Op(Op::Equality)),
+    ("==", Lexeme::Op(Op::Equality)),
+    ("->", Lexeme::Op(Op::Implication)),
+    ("=>", Lexeme::Op(Op::Implication)),
+    ("&&", Lexeme::Op(Op::And)),
+    ("||", Lexeme::Op(Op::Or)),
+    ("=", Lexeme::Op(Op::Equality)),
+    ("!", Lexeme::Not),
+    ("~", Lexeme::Not),
+    ("&", Lexeme::Op(Op::And)),
+    ("*", Lexeme::Op(Op::And)),
+    ("^", Lexeme::Op(Op::Xor)),
+    ("|", Lexeme::Op(Op::Or)),
+    ("+", Lexeme::Op(Op::Or)),
+    ("(", Lexeme::Open),
+    ("[", Lexeme::Open),
+    ("{", Lexeme::Open),
+    (")", Lexeme::Close),
+    ("]", Lexeme::Close),
+    ("}", Lexeme::Close),
+];
+
+fn lex(src: &str) -> Vec<Lexeme> {
+    let mut out = Vec::new();
+    let mut rest = src;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return out;
+        }
+
+        if let Some((s, lex)) = SYMBOLS.iter().find(|(s, _)| rest.starts_with(s)) {
+            out.push(lex.clone());
+            rest = &rest[s.len()..];
+            continue;
+        }
+
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        assert!(len != 0, "unexpected {:?} in {:?}", rest, src);
+        let word = &rest[..len];
+        out.push(match word.to_ascii_lowercase().as_str() {
+            "true" => Lexeme::Lit(true),
+            "false" => Lexeme::Lit(false),
+            "not" => Lexeme::Not,
+            "and" => Lexeme::Op(Op::And),
+            "xor" => Lexeme::Op(Op::Xor),
+            "or" => Lexeme::Op(Op::Or),
+            _ => Lexeme::Var(word.into()),
+        });
+        rest = &rest[len..];
+    }
+}
+
+struct RefParser {
+    lexemes: Vec<Lexeme>,
+    pos: usize,
+}
+
+impl RefParser {
+    fn parse(src: &str) -> Ref {
+        let mut p = RefParser {
+            lexemes: lex(src),
+            pos: 0,
+        };
+        let expr = p.binary(Op::Equality);
+        assert_eq!(p.pos, p.lexemes.len(), "trailing input in {:?}", src);
+        expr
+    }
+
+    fn next(&mut self) -> Option<Lexeme> {
+        let lex = self.lexemes.get(self.pos).cloned();
+        self.pos += 1;
+        lex
+    }
+
+    /// An expression of operators at least as tight as `op`, where
+    /// the right operand is parsed at the same level, so that
+    /// chains group to the right
+    fn binary(&mut self, op: Op) -> Ref {
+        let tighter = match op {
+            Op::Equality => Some(Op::Implication),
+            Op::Implication => Some(Op::Or),
+            Op::Or => Some(Op::Xor),
+            Op::Xor => Some(Op::And),
+            Op::And => None,
+        };
+        let lhs = match tighter {
+            Some(t) => self.binary(t),
+            None => self.unary(),
+        };
+        if self.lexemes.get(self.pos) == Some(&Lexeme::Op(op)) {
+            self.pos += 1;
+            let rhs = self.binary(op);
+            Ref::Bin(op, Box::new(lhs), Box::new(rhs))
+        } else {
+            lhs
+        }
+    }
+
+    fn unary(&mut self) -> Ref {
+        match self.next() {
+            Some(Lexeme::Lit(v)) => Ref::Lit(v),
+            Some(Lexeme::Var(name)) => Ref::Var(name),
+            Some(Lexeme::Not) => Ref::Not(Box::new(self.unary())),
+            Some(Lexeme::Open) => {
+                let expr = self.binary(Op::Equality);
+                assert_eq!(self.next(), Some(Lexeme::Close), "unclosed paren");
+                expr
+            }
+            l => panic!("expected a value, found {:?}", l),
+        }
+    }
+}
+
+impl Ref {
+    /// The variables, in the order they first appear
+    fn vars(&self, out: &mut Vec<String>) {
+        match self {
+            Ref::Lit(_) => {}
+            Ref::Var(name) => {
+                if !out.contains(name) {
+                    out.push(name.clone());
+                }
+            }
+            Ref::Not(v) => v.vars(out),
+            Ref::Bin(_, l, r) => {
+                l.vars(out);
+                r.vars(out);
+            }
+        }
+    }
+
+    fn eval(&self, var: &dyn Fn(&str) -> bool) -> bool {
+        match self {
+            Ref::Lit(v) => *v,
+            Ref::Var(name) => var(name),
+            Ref::Not(v) => !v.eval(var),
+            Ref::Bin(op, l, r) => {
+                let (l, r) = (l.eval(var), r.eval(var));
+                match op {
+                    Op::And => l && r,
+                    Op::Xor => l ^ r,
+                    Op::Or => l || r,
+                    Op::Implication => !l || r,
+                    Op::Equality => l == r,
+                }
+            }
+        }
+    }
+
+    /// The truth table over the given variables, where the
+    /// bit `n` of a state is the value of `vars[n]`
+    fn table(&self, vars: &[&str]) -> Vec<bool> {
+        (0..1usize << vars.len())
+            .map(|s| {
+                self.eval(&|name| {
+                    let n = vars.iter().position(|v| *v == name).expect("known var");
+                    s >> n & 1 == 1
+                })
+            })
+            .collect()
+    }
+}
+
+/// The truth table of the production evaluator, in the same order
+fn table<E: Expression>(tester: &Tester<E>) -> Vec<bool> {
+    (0..tester.max_iter())
+        .map(|s| tester.expr().eval(s))
+        .collect()
+}
+
+/// Check that the production parser and evaluator agree with
+/// the reference on `src`, variable order included
+fn assert_agrees(src: &str) {
+    let reference = RefParser::parse(src);
+    let tester = Tester::parse(src);
+
+    let mut vars = Vec::new();
+    reference.vars(&mut vars);
+    assert_eq!(tester.vars(), vars.as_slice(), "variables of {:?}", src);
+    assert_eq!(
+        table(&tester),
+        reference.table(tester.vars()),
+        "truth table of {:?}",
+        src
+    );
+}
+
+/// Check that printing `tokens` and parsing the result
+/// back gives an expression with the same truth table
+fn assert_round_trips(tokens: &Tokens) {
+    let printed = tokens.to_string();
+    let expected: Vec<bool> = (0..1usize << tokens.var_count())
+        .map(|s| tokens.eval(s))
+        .collect();
+    assert_eq!(
+        RefParser::parse(&printed).table(tokens.vars()),
+        expected,
+        "{:?} printed as {:?}",
+        tokens,
+        printed
+    );
+    assert_agrees(&printed);
+
+    for name in TokenLiterals::profile_names() {
+        let literals = TokenLiterals::profile(name).expect("known profile");
+        let printed = tokens.to_string_with(&literals);
+        let reparsed = Tester::parse_with_literals(&printed, literals);
+        let reparsed = reparsed.expr().over_vars(tokens.vars());
+        let actual: Vec<bool> = (0..1usize << tokens.var_count())
+            .map(|s| reparsed.eval(s))
+            .collect();
+        assert_eq!(actual, expected, "{:?} printed as {:?}", tokens, printed);
+    }
+}
+
+#[test]
+fn corpus_agrees_with_reference() {
+    for src in CORPUS {
+        assert_agrees(src);
+    }
+}
+
+#[test]
+fn chains_group_to_the_right() {
+    // false only when a and b are true and c is false,
+    // where bit `n` of the state is the `n`th variable
+    let tester = Tester::parse("a -> b -> c");
+    let expected: Vec<bool> = (0..8).map(|s| s != 0b011).collect();
+    assert_eq!(table(&tester), expected, "a -> b -> c");
+
+    // false whenever `c` is false and `a -> b` is true
+    let tester = Tester::parse("(a -> b) -> c");
+    let expected: Vec<bool> = (0..8).map(|s| s & 0b100 != 0 || s == 0b001).collect();
+    assert_eq!(table(&tester), expected, "(a -> b) -> c");
+}
+
+#[test]
+fn corpus_round_trips() {
+    for src in CORPUS {
+        assert_round_trips(Tester::parse(src).expr());
+    }
+}
+
+/// Round trip the expressions generated from the first `seeds`
+/// seeds, over every operator and over only `!`, `->` and `=`,
+/// so that long chains of them show up
+fn assert_random_round_trips(seeds: u64) {
+    let generator = Generator::new(4).depth(5);
+    for seed in 0..seeds {
+        let tokens = generator.generate(SplitMix64::new(seed));
+        assert_round_trips(&tokens);
+    }
+
+    let generator =
+        Generator::new(3)
+            .depth(6)
+            .operators(&[Token::Not, Token::Implication, Token::Equality]);
+    for seed in 0..seeds {
+        let tokens = generator.generate(SplitMix64::new(seed));
+        assert_round_trips(&tokens);
+    }
+}
+
+#[test]
+fn random_expressions_agree_and_round_trip() {
+    assert_random_round_trips(50);
+}
+
+/// The same with many more seeds, left out by default,
+/// run it with `cargo test -- --ignored`
+#[test]
+#[ignore]
+fn many_random_expressions_agree_and_round_trip() {
+    assert_random_round_trips(1000);
+}
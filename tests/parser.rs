@@ -0,0 +1,91 @@
+//! Checks the parsers against known truth tables, and
+//! that malformed input is reported where it goes wrong
+#![cfg(all(feature = "tester", feature = "parsing"))]
+
+use truth_tester::{
+    eval::Tester,
+    parsing::{Notation, ParseErrorKind, ParseErrorKind::*, Parser},
+};
+
+/// The truth table of an expression, where
+/// bit `n` of a state is the `n`th variable
+fn table(src: &str) -> Vec<bool> {
+    Tester::parse(src).eval().map(|(_, v)| v).collect()
+}
+
+#[test]
+fn parens_group_binary_operators() {
+    // c and either of a or b
+    let expected: Vec<bool> = (0..8).map(|s| s & 0b100 != 0 && s & 0b011 != 0).collect();
+    assert_eq!(table("(a | b) & c"), expected);
+    assert_eq!(table("c & (a | b)"), table("c & a | c & b"));
+}
+
+#[test]
+fn nested_parens_keep_their_operators() {
+    // a and not both b and c
+    let expected: Vec<bool> = (0..8)
+        .map(|s| s & 0b001 != 0 && s & 0b110 != 0b110)
+        .collect();
+    assert_eq!(table("a & !((b & c))"), expected);
+    assert_eq!(table("(a) & (b)"), table("a & b"));
+}
+
+/// Check that parsing `src` in `notation` fails at
+/// the byte `offset` for the given reason
+fn assert_fails(notation: Notation, src: &str, offset: usize, kind: ParseErrorKind) {
+    let err = Parser::parse(src)
+        .try_parse_notation(notation)
+        .expect_err(src);
+    assert_eq!((err.offset(), err.kind()), (offset, kind), "{:?}", src);
+}
+
+#[test]
+fn notations_agree() {
+    let infix = Tester::parse("a & (b | !c)").truth_table();
+    for (notation, src) in [
+        (Notation::Prefix, "& a | b ! c"),
+        (Notation::Postfix, "a b c ! | &"),
+        (Notation::SExpression, "(and a (or b (not c)))"),
+    ]
+    .iter()
+    {
+        let tester = Tester::try_parse_notation(src, *notation).expect(src);
+        assert_eq!(tester.truth_table(), infix, "{:?}", src);
+    }
+}
+
+#[test]
+fn reports_malformed_prefix() {
+    assert_fails(Notation::Prefix, "", 0, MissingOperand);
+    assert_fails(Notation::Prefix, "& a", 3, MissingOperand);
+    assert_fails(Notation::Prefix, "& a b c", 6, TrailingInput);
+    assert_fails(Notation::Prefix, "& (a b)", 2, UnexpectedParen);
+}
+
+#[test]
+fn reports_malformed_postfix() {
+    assert_fails(Notation::Postfix, "", 0, MissingOperand);
+    assert_fails(Notation::Postfix, "a &", 2, MissingOperand);
+    assert_fails(Notation::Postfix, "a b", 3, MissingOperator);
+    assert_fails(Notation::Postfix, "a b &)", 5, UnexpectedParen);
+}
+
+#[test]
+fn reports_malformed_s_expressions() {
+    assert_fails(Notation::SExpression, "", 0, MissingOperand);
+    assert_fails(Notation::SExpression, "(and a (or b", 7, UnclosedParen);
+    assert_fails(Notation::SExpression, "(", 0, UnclosedParen);
+    assert_fails(Notation::SExpression, "(a b)", 1, MissingOperator);
+    assert_fails(Notation::SExpression, "(and)", 4, MissingOperand);
+    assert_fails(Notation::SExpression, "(not a b)", 7, ExtraOperand);
+    assert_fails(Notation::SExpression, "(and a b) c", 10, TrailingInput);
+    assert_fails(Notation::SExpression, ")", 0, UnopenedParen);
+    assert_fails(Notation::SExpression, "and", 0, MissingOperand);
+}
+
+#[test]
+#[should_panic(expected = "Invalid expression at offset 3: expected a value")]
+fn panics_on_malformed_notation() {
+    Parser::parse("& a").parse_notation(Notation::Prefix);
+}
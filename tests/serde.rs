@@ -0,0 +1,117 @@
+#![cfg(all(feature = "tester", feature = "serde"))]
+
+use truth_tester::{
+    eval::{Tester, TruthTable},
+    parsing::{OwnedTokens, Parser, TokenLiterals, Tokens},
+};
+
+#[test]
+fn tokens_round_trip() {
+    let tokens = Parser::parse("a & !b -> c = a").shunting_yard();
+    let json = serde_json::to_string(&tokens).unwrap();
+    assert_eq!(serde_json::from_str::<Tokens>(&json).unwrap(), tokens);
+
+    let owned: OwnedTokens = serde_json::from_reader(json.as_bytes()).unwrap();
+    assert_eq!(owned.tokens(), tokens);
+    assert_eq!(serde_json::to_string(&owned).unwrap(), json);
+}
+
+#[test]
+fn owned_tokens_read_escaped_names() {
+    let json = r#"{"tokens":[{"Var":["\u00e9",0]},"Not"],"vars":["\u00e9"]}"#;
+    assert!(serde_json::from_str::<Tokens>(json).is_err());
+
+    let owned: OwnedTokens = serde_json::from_str(json).unwrap();
+    assert_eq!(owned.tokens().to_string(), "!é");
+}
+
+#[test]
+fn tokens_reject_bad_variables() {
+    let past = r#"{"tokens":[{"Var":["a",1]}],"vars":["a"]}"#;
+    let renamed = r#"{"tokens":[{"Var":["b",0]}],"vars":["a"]}"#;
+    for json in [past, renamed].iter() {
+        assert!(serde_json::from_str::<Tokens>(json).is_err(), "{}", json);
+        assert!(
+            serde_json::from_str::<OwnedTokens>(json).is_err(),
+            "{}",
+            json
+        );
+    }
+}
+
+#[test]
+fn tokens_reject_malformed_expressions() {
+    let cases = [
+        r#"{"tokens":[],"vars":[]}"#,
+        r#"{"tokens":["And"],"vars":[]}"#,
+        r#"{"tokens":[{"Literal":true},{"Literal":false}],"vars":[]}"#,
+        r#"{"tokens":["LParen",{"Literal":true},"RParen"],"vars":[]}"#,
+    ];
+    for json in cases.iter() {
+        assert!(serde_json::from_str::<Tokens>(json).is_err(), "{}", json);
+        assert!(
+            serde_json::from_str::<OwnedTokens>(json).is_err(),
+            "{}",
+            json
+        );
+    }
+}
+
+#[test]
+fn truth_table_round_trips() {
+    for src in ["a", "a ^ b ^ c", "a & b & c & d & e & f & g"].iter() {
+        let table = Tester::parse(src).truth_table();
+        let json = serde_json::to_string(&table).unwrap();
+        assert_eq!(serde_json::from_str::<TruthTable>(&json).unwrap(), table);
+    }
+}
+
+#[test]
+fn truth_table_rejects_bad_bits() {
+    let cases = [
+        // one word short, and one too many
+        r#"{"var_count":7,"bits":[0]}"#,
+        r#"{"var_count":2,"bits":[0,0]}"#,
+        // a state past the fourth
+        r#"{"var_count":2,"bits":[16]}"#,
+        r#"{"var_count":64,"bits":[]}"#,
+    ];
+    for json in cases.iter() {
+        assert!(
+            serde_json::from_str::<TruthTable>(json).is_err(),
+            "{}",
+            json
+        );
+    }
+}
+
+#[test]
+fn literals_round_trip() {
+    for name in TokenLiterals::profile_names() {
+        let literals = TokenLiterals::profile(name).unwrap();
+        let json = serde_json::to_string(&literals).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TokenLiterals>(&json).unwrap(),
+            literals
+        );
+    }
+    // left out spellings keep their defaults
+    assert_eq!(
+        serde_json::from_str::<TokenLiterals>("{}").unwrap(),
+        TokenLiterals::default()
+    );
+}
+
+#[test]
+fn literals_reject_unlexable_spellings() {
+    let cases = [
+        r#"{"and":[]}"#,
+        r#"{"or":[""]}"#,
+        r#"{"not":["n o t"]}"#,
+        r#"{"and":["|"]}"#,
+    ];
+    for json in cases.iter() {
+        let err = serde_json::from_str::<TokenLiterals>(json).unwrap_err();
+        assert!(err.is_data(), "{}: {}", json, err);
+    }
+}
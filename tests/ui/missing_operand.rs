@@ -0,0 +1,5 @@
+use truth_tester::bool_expr;
+
+fn main() {
+    let _ = bool_expr!(a & (b |));
+}